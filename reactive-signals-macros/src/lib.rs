@@ -0,0 +1,27 @@
+//!
+//! Derive macros for [reactive-signals](https://crates.io/crates/reactive-signals).
+//! Use them through the re-exports in reactive-signals.
+//!
+mod scoped;
+mod store;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// See the `Store` trait in reactive-signals.
+#[proc_macro_derive(Store, attributes(store))]
+pub fn derive_store(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    store::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// See the `Scope` documentation in reactive-signals.
+#[proc_macro_derive(Scoped, attributes(scoped))]
+pub fn derive_scoped(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    scoped::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
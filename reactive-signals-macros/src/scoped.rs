@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse::ParseStream, DeriveInput, Error, Ident, LitStr, Path, Result, Token};
+
+/// The arguments of the `#[scoped(..)]` attribute
+struct Args {
+    /// The type of the scope the value is attached to
+    parent: Path,
+    /// The name of the generated scope type
+    name: Option<Ident>,
+    no_eq: bool,
+}
+
+impl Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let parent = input.parse()?;
+        let mut args = Args {
+            parent,
+            name: None,
+            no_eq: false,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            let arg: Ident = input.parse()?;
+            if arg == "name" {
+                input.parse::<Token![=]>()?;
+                let name: LitStr = input.parse()?;
+                args.name = Some(name.parse()?);
+            } else if arg == "no_eq" {
+                args.no_eq = true;
+            } else {
+                return Err(Error::new_spanned(arg, "expected `name` or `no_eq`"));
+            }
+        }
+        Ok(args)
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Scoped can't be derived for generic types",
+        ));
+    }
+    let attr = input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("scoped"))
+        .ok_or_else(|| {
+            Error::new_spanned(
+                name,
+                "the scope type is missing: add #[scoped(Scope)] or #[scoped(SomeScope)]",
+            )
+        })?;
+    let Args {
+        parent,
+        name: wrapper,
+        no_eq,
+    } = attr.parse_args_with(Args::parse)?;
+
+    let wrapper = wrapper.unwrap_or_else(|| format_ident!("{name}Scope"));
+    let accessor = format_ident!("{}", snake_case(&name.to_string()));
+    let (sig_ty, create) = if no_eq {
+        (
+            quote!(::reactive_signals::types::Data<#name>),
+            quote!(::reactive_signals::store::data),
+        )
+    } else {
+        (
+            quote!(::reactive_signals::types::EqData<#name>),
+            quote!(::reactive_signals::store::eq_data),
+        )
+    };
+    let doc = format!(
+        "A [{parent}] with a [{name}] attached to it.",
+        parent = quote!(#parent)
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #wrapper<RT: ::reactive_signals::runtimes::Runtime> {
+            parent: #parent<RT>,
+            #accessor: ::reactive_signals::Attached<#sig_ty>,
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::clone::Clone for #wrapper<RT> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::marker::Copy for #wrapper<RT> {}
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::ops::Deref for #wrapper<RT> {
+            type Target = #parent<RT>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.parent
+            }
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::convert::From<#wrapper<RT>>
+            for ::reactive_signals::Scope<RT>
+        {
+            fn from(sc: #wrapper<RT>) -> Self {
+                ::reactive_signals::Scope::from(sc.parent)
+            }
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> #wrapper<RT> {
+            pub fn #accessor(&self) -> ::reactive_signals::Signal<#sig_ty, RT> {
+                self.#accessor.signal(::reactive_signals::Scope::from(*self))
+            }
+        }
+
+        impl #name {
+            /// Attaches the value to the scope, as a signal
+            pub fn attach_to<RT: ::reactive_signals::runtimes::Runtime>(
+                self,
+                sc: #parent<RT>,
+            ) -> #wrapper<RT> {
+                let sig = #create(::reactive_signals::Scope::from(sc), self);
+                #wrapper {
+                    parent: sc,
+                    #accessor: ::reactive_signals::Attached::new(sig),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,191 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Field, Fields, Result, Type};
+
+/// How a field is stored
+enum FieldKind {
+    /// In a signal that only notifies when the value changes
+    Eq,
+    /// In a signal that always notifies
+    NoEq,
+    /// In the store of the field's type
+    Nested,
+}
+
+impl FieldKind {
+    fn of(field: &Field) -> Result<Self> {
+        let mut kind = FieldKind::Eq;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("store")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("no_eq") {
+                    kind = FieldKind::NoEq;
+                    Ok(())
+                } else if meta.path.is_ident("nested") {
+                    kind = FieldKind::Nested;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `no_eq` or `nested`"))
+                }
+            })?;
+        }
+        Ok(kind)
+    }
+
+    fn store_type(&self, ty: &Type) -> TokenStream {
+        match self {
+            FieldKind::Eq => {
+                quote!(::reactive_signals::Signal<::reactive_signals::types::EqData<#ty>, RT>)
+            }
+            FieldKind::NoEq => {
+                quote!(::reactive_signals::Signal<::reactive_signals::types::Data<#ty>, RT>)
+            }
+            FieldKind::Nested => quote!(<#ty as ::reactive_signals::Store>::Store<RT>),
+        }
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let store = format_ident!("{name}Store");
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Store can't be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "Store can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "Store can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut idents = Vec::new();
+    let mut store_fields = Vec::new();
+    let mut accessors = Vec::new();
+    let mut creates = Vec::new();
+    let mut sets = Vec::new();
+    let mut reads = Vec::new();
+
+    for field in fields {
+        let kind = FieldKind::of(field)?;
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let fvis = &field.vis;
+        let store_ty = kind.store_type(ty);
+
+        store_fields.push(quote!(#ident: #store_ty));
+        accessors.push(quote! {
+            #fvis fn #ident(&self) -> #store_ty {
+                self.#ident
+            }
+        });
+        creates.push(match kind {
+            FieldKind::Eq => quote!(::reactive_signals::store::eq_data(sx, #ident)),
+            FieldKind::NoEq => quote!(::reactive_signals::store::data(sx, #ident)),
+            FieldKind::Nested => quote!(<#ty as ::reactive_signals::Store>::into_store(#ident, sx)),
+        });
+        sets.push(match kind {
+            FieldKind::Nested => {
+                quote!(<#ty as ::reactive_signals::Store>::set_store(&self.#ident, #ident))
+            }
+            _ => quote!(self.#ident.set(#ident)),
+        });
+        reads.push(match kind {
+            FieldKind::Nested => {
+                quote!(<#ty as ::reactive_signals::Store>::from_store(&self.#ident))
+            }
+            _ => quote!(self.#ident.cloned()),
+        });
+        idents.push(ident);
+    }
+
+    let doc = format!("The reactive store of a [{name}], with a signal for each field.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #store<RT: ::reactive_signals::runtimes::Runtime> {
+            sx: ::reactive_signals::Scope<RT>,
+            #(#store_fields,)*
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::clone::Clone for #store<RT> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> ::core::marker::Copy for #store<RT> {}
+
+        impl<RT: ::reactive_signals::runtimes::Runtime> #store<RT> {
+            pub fn new(sx: ::reactive_signals::Scope<RT>, value: #name) -> Self {
+                let #name { #(#idents),* } = value;
+                Self {
+                    sx,
+                    #(#idents: #creates,)*
+                }
+            }
+
+            #(#accessors)*
+
+            /// Sets the value of all the fields. Only the subscribers
+            /// of the changed fields are notified.
+            pub fn set(&self, value: #name) {
+                self.sx.batch(|| {
+                    let #name { #(#idents),* } = value;
+                    #(#sets;)*
+                })
+            }
+
+            /// A clone of the whole value. Subscribes the running
+            /// func signal to all the fields.
+            pub fn cloned(&self) -> #name {
+                #name {
+                    #(#idents: #reads,)*
+                }
+            }
+
+            /// Applies the function to a clone of the whole value and sets it.
+            pub fn update(&self, f: impl FnOnce(&mut #name)) {
+                let mut value = self.sx.untrack(|| self.cloned());
+                f(&mut value);
+                self.set(value);
+            }
+        }
+
+        impl ::reactive_signals::Store for #name {
+            type Store<RT: ::reactive_signals::runtimes::Runtime> = #store<RT>;
+
+            fn into_store<RT: ::reactive_signals::runtimes::Runtime>(
+                self,
+                sx: ::reactive_signals::Scope<RT>,
+            ) -> Self::Store<RT> {
+                #store::new(sx, self)
+            }
+
+            fn set_store<RT: ::reactive_signals::runtimes::Runtime>(
+                store: &Self::Store<RT>,
+                value: Self,
+            ) {
+                store.set(value)
+            }
+
+            fn from_store<RT: ::reactive_signals::runtimes::Runtime>(store: &Self::Store<RT>) -> Self {
+                store.cloned()
+            }
+        }
+    })
+}
@@ -13,7 +13,10 @@ impl NodeSlotAvailability {
 
     #[inline]
     pub(crate) fn get_available<T>(&mut self, vec: &[Node<T>]) -> Option<NodeId> {
-        get_available(&mut self.0, |i| !(i == 0 || vec[i].is_used())).map(NodeId::from)
+        get_available(&mut self.0, |i| {
+            !(i == 0 || i >= vec.len() || vec[i].is_used() || vec[i].is_retired())
+        })
+        .map(NodeId::from)
     }
 
     #[inline]
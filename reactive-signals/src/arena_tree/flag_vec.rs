@@ -21,7 +21,7 @@ impl FlagVec {
     pub fn get(&self, index: usize) -> bool {
         let slot = index / BITS;
         let idx = index % BITS;
-        (self.0[slot] & AND_MASK[idx]) != 0
+        (self.0[slot] & OR_MASK[idx]) != 0
     }
 }
 
@@ -60,41 +60,6 @@ const OR_MASK: [u32; 32] = [
     0b0000_0000_0000_0000_0000_0000_0000_0001,
 ];
 
-const AND_MASK: [u32; 32] = [
-    0b0111_1111_1111_1111_1111_1111_1111_1111,
-    0b1011_1111_1111_1111_1111_1111_1111_1111,
-    0b1101_1111_1111_1111_1111_1111_1111_1111,
-    0b1110_1111_1111_1111_1111_1111_1111_1111,
-    0b1111_0111_1111_1111_1111_1111_1111_1111,
-    0b1111_1011_1111_1111_1111_1111_1111_1111,
-    0b1111_1101_1111_1111_1111_1111_1111_1111,
-    0b1111_1110_1111_1111_1111_1111_1111_1111,
-    0b1111_1111_0111_1111_1111_1111_1111_1111,
-    0b1111_1111_1011_1111_1111_1111_1111_1111,
-    0b1111_1111_1101_1111_1111_1111_1111_1111,
-    0b1111_1111_1110_1111_1111_1111_1111_1111,
-    0b1111_1111_1111_0111_1111_1111_1111_1111,
-    0b1111_1111_1111_1011_1111_1111_1111_1111,
-    0b1111_1111_1111_1101_1111_1111_1111_1111,
-    0b1111_1111_1111_1110_1111_1111_1111_1111,
-    0b1111_1111_1111_1111_0111_1111_1111_1111,
-    0b1111_1111_1111_1111_1011_1111_1111_1111,
-    0b1111_1111_1111_1111_1101_1111_1111_1111,
-    0b1111_1111_1111_1111_1110_1111_1111_1111,
-    0b1111_1111_1111_1111_1111_0111_1111_1111,
-    0b1111_1111_1111_1111_1111_1011_1111_1111,
-    0b1111_1111_1111_1111_1111_1101_1111_1111,
-    0b1111_1111_1111_1111_1111_1110_1111_1111,
-    0b1111_1111_1111_1111_1111_1111_0111_1111,
-    0b1111_1111_1111_1111_1111_1111_1011_1111,
-    0b1111_1111_1111_1111_1111_1111_1101_1111,
-    0b1111_1111_1111_1111_1111_1111_1110_1111,
-    0b1111_1111_1111_1111_1111_1111_1111_0111,
-    0b1111_1111_1111_1111_1111_1111_1111_1011,
-    0b1111_1111_1111_1111_1111_1111_1111_1101,
-    0b1111_1111_1111_1111_1111_1111_1111_1110,
-];
-
 #[test]
 fn test_flagvec() {
     let mut v = FlagVec::with_size(62);
@@ -117,3 +82,18 @@ fn test_flagvec() {
     v.set(62);
     assert_eq!(v.0[1], 0b1000_0010_0000_0000_0000_0000_0000_0010);
 }
+
+#[test]
+fn test_flagvec_get() {
+    let mut v = FlagVec::with_size(62);
+    v.set(0);
+    v.set(38);
+
+    // only the bit of the index is read, not the others of its slot
+    assert!(v.get(0));
+    assert!(!v.get(1));
+    assert!(!v.get(31));
+    assert!(!v.get(32));
+    assert!(v.get(38));
+    assert!(!v.get(39));
+}
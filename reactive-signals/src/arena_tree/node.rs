@@ -1,16 +1,28 @@
-use std::{fmt, num::NonZeroU16};
+use std::fmt;
 
+#[cfg(not(feature = "large-ids"))]
+type RawId = u16;
+#[cfg(not(feature = "large-ids"))]
+type NonZeroRawId = std::num::NonZeroU16;
+
+#[cfg(feature = "large-ids")]
+type RawId = u32;
+#[cfg(feature = "large-ids")]
+type NonZeroRawId = std::num::NonZeroU32;
+
+/// The index of a node in the tree. It is a u16 by default and a u32
+/// with the `large-ids` feature.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct NodeId(NonZeroU16);
+pub struct NodeId(NonZeroRawId);
 
 impl From<usize> for NodeId {
     fn from(id: usize) -> Self {
-        Self(unsafe { NonZeroU16::new_unchecked(id as u16 + 1) })
+        Self(unsafe { NonZeroRawId::new_unchecked(id as RawId + 1) })
     }
 }
 
 impl NodeId {
-    pub(crate) const MAX: usize = u16::MAX as usize - 1;
+    pub(crate) const MAX: usize = RawId::MAX as usize - 1;
 
     #[inline]
     pub(crate) fn index(self) -> usize {
@@ -19,11 +31,11 @@ impl NodeId {
 
     #[inline]
     pub(crate) fn root() -> Self {
-        Self(unsafe { NonZeroU16::new_unchecked(1) })
+        Self(unsafe { NonZeroRawId::new_unchecked(1) })
     }
 
     #[inline]
-    pub fn as_raw(&self) -> u16 {
+    pub fn as_raw(&self) -> RawId {
         self.0.get()
     }
 }
@@ -34,18 +46,30 @@ impl fmt::Debug for NodeId {
     }
 }
 
+/// The generation of a slot that is no longer used, as reusing it once more would
+/// wrap its generation around and match the stale references to its first nodes.
+pub(crate) const RETIRED: u16 = u16::MAX;
+
 #[derive(Default, Debug)]
 pub struct Node<T> {
     pub data: T,
     pub parent: Option<NodeId>,
     pub last_child: Option<NodeId>,
     pub prev_sibling: Option<NodeId>,
+    /// Incremented each time the node is reused, so that
+    /// stale references to the node can be detected.
+    /// The slot is retired when it reaches [RETIRED].
+    pub generation: u16,
 }
 
 impl<T> Node<T> {
     pub fn is_used(&self) -> bool {
         self.parent.is_some()
     }
+
+    pub fn is_retired(&self) -> bool {
+        self.generation == RETIRED
+    }
 }
 impl<T> Node<T> {
     pub fn new(data: T) -> Self {
@@ -54,6 +78,7 @@ impl<T> Node<T> {
             parent: None,
             last_child: None,
             prev_sibling: None,
+            generation: 0,
         }
     }
 
@@ -61,6 +86,7 @@ impl<T> Node<T> {
         self.parent = None;
         self.last_child = None;
         self.prev_sibling = None;
+        self.generation = self.generation.saturating_add(1);
     }
 }
 
@@ -71,6 +97,7 @@ impl<T: Clone> Clone for Node<T> {
             parent: self.parent,
             last_child: self.last_child,
             prev_sibling: self.prev_sibling,
+            generation: self.generation,
         }
     }
 }
@@ -78,5 +105,6 @@ impl<T: Clone> Clone for Node<T> {
 #[test]
 fn test_node_size() {
     let node = Node::<u32>::default();
-    assert_eq!(std::mem::size_of_val(&node), 12);
+    let size = if cfg!(feature = "large-ids") { 20 } else { 12 };
+    assert_eq!(std::mem::size_of_val(&node), size);
 }
@@ -55,3 +55,46 @@ fn reuse_ids() {
     "###);
     assert_snapshot!(tree.dump_used(), @"[0] 0, [1] 1, [2] 11, [3] 3, [4] 31");
 }
+
+#[test]
+fn retire_slot() {
+    use super::Tree;
+
+    let mut tree = Tree::create_and_init(0);
+    let first = tree.add_child(tree.root(), 1);
+
+    let mut child = first;
+    for generation in 1..u16::MAX {
+        tree.discard(child, |_| {});
+        child = tree.add_child(tree.root(), 1);
+        assert_eq!((child, tree.generation(child)), (first, generation));
+    }
+
+    // the slot is retired instead of its generation wrapping around to 0
+    tree.discard(child, |_| {});
+    let other = tree.add_child(tree.root(), 2);
+    assert_ne!(other, first);
+    assert!(!tree.is_alive(first, 0));
+    assert_snapshot!(tree.dump_used(), @"[0] 0, [2] 2");
+
+    // and stays retired when the tree is reused
+    tree.discard_all();
+    tree.init(0);
+    tree.add_child(tree.root(), 1);
+    tree.add_child(tree.root(), 2);
+    assert_snapshot!(tree.dump_used(), @"[0] 0, [2] 1, [3] 2");
+}
+
+#[test]
+fn retire_root() {
+    use super::Tree;
+
+    let mut tree = Tree::create_and_init(0);
+    for _ in 1..u16::MAX {
+        tree.discard_all();
+        assert!(!tree.is_retired());
+        tree.init(0);
+    }
+    tree.discard_all();
+    assert!(tree.is_retired());
+}
@@ -1,5 +1,8 @@
 use super::{
-    availability::NodeSlotAvailability, flag_vec::FlagVec, iter::MutDepthFirstIter, Node, NodeId,
+    availability::NodeSlotAvailability,
+    flag_vec::FlagVec,
+    iter::MutDepthFirstIter,
+    node::{Node, NodeId, RETIRED},
 };
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 
@@ -8,6 +11,11 @@ pub struct Tree<T> {
     pub(crate) initialized: bool,
     pub(crate) nodes: Vec<Node<T>>,
     pub(crate) availability: NodeSlotAvailability,
+    /// The generation of the next node created in each slot. Set when the tree
+    /// is discarded so that nodes of a previous use are not mistaken for the
+    /// new ones, as the generation of a slot only ever increases until the slot
+    /// is retired.
+    pub(crate) generations: Vec<u16>,
 }
 
 impl<T> Index<NodeId> for Tree<T> {
@@ -42,6 +50,7 @@ impl<T: Clone> Clone for Tree<T> {
             initialized: self.initialized,
             nodes: self.nodes.clone(),
             availability: Default::default(),
+            generations: self.generations.clone(),
         }
     }
 }
@@ -62,9 +71,19 @@ impl<T: Default> Tree<T> {
             );
             id
         } else {
+            // the retired slots are kept, as they can't be used
+            while self.next_generation(self.nodes.len()) == RETIRED {
+                self.nodes.push(Node {
+                    generation: RETIRED,
+                    ..Default::default()
+                });
+            }
             let idx = self.nodes.len();
             debug_assert!(idx <= NodeId::MAX, "too many nodes");
-            self.nodes.push(Default::default());
+            self.nodes.push(Node {
+                generation: self.next_generation(idx),
+                ..Default::default()
+            });
             idx.into()
         }
     }
@@ -73,11 +92,18 @@ impl<T: Default> Tree<T> {
         self.initialized
     }
 
+    /// Whether the tree can't be initialized again, as the slot of
+    /// its root, which can't be moved, is retired.
+    pub fn is_retired(&self) -> bool {
+        !self.initialized && self.next_generation(0) == RETIRED
+    }
+
     pub fn create() -> Self {
         Self {
             initialized: false,
             nodes: vec![],
             availability: Default::default(),
+            generations: vec![],
         }
     }
 
@@ -93,9 +119,13 @@ impl<T: Default> Tree<T> {
             !self.initialized,
             "tree already initialized. did you forget to discard it before reusing it?"
         );
+        debug_assert!(!self.is_retired(), "the root slot of the tree is retired");
         let root_id = self.availability.init();
 
-        self.nodes.push(Node::new(data));
+        self.nodes.push(Node {
+            generation: self.next_generation(0),
+            ..Node::new(data)
+        });
         self.initialized = true;
         root_id
     }
@@ -104,6 +134,34 @@ impl<T: Default> Tree<T> {
         NodeId::root()
     }
 
+    #[inline]
+    pub fn generation(&self, id: NodeId) -> u16 {
+        self.nodes[id.index()].generation
+    }
+
+    /// Whether the node is still the one with the given generation,
+    /// i.e. it has not been discarded.
+    #[inline]
+    pub fn is_alive(&self, id: NodeId, generation: u16) -> bool {
+        self.initialized
+            && self
+                .nodes
+                .get(id.index())
+                .is_some_and(|node| node.generation == generation)
+    }
+
+    /// The children of the node, in the order they were added.
+    pub fn children(&self, id: NodeId) -> Vec<NodeId> {
+        let mut children = Vec::new();
+        let mut child = self.nodes[id.index()].last_child;
+        while let Some(id) = child {
+            children.push(id);
+            child = self.nodes[id.index()].prev_sibling;
+        }
+        children.reverse();
+        children
+    }
+
     #[cfg(any(test, feature = "profile"))]
     pub fn iter_from(&self, id: NodeId) -> super::iter::DepthFirstIter<T> {
         super::iter::DepthFirstIter::new(self, id)
@@ -167,23 +225,39 @@ impl<T: Default> Tree<T> {
             "tree cannot be discarded because it is not initialized"
         );
         self.availability.discard();
+        if self.generations.len() < self.nodes.len() {
+            self.generations.resize(self.nodes.len(), 0);
+        }
+        for (generation, node) in self.generations.iter_mut().zip(&self.nodes) {
+            *generation = node.generation.saturating_add(1);
+        }
         self.nodes.clear();
         self.initialized = false;
     }
 
+    /// The generation of a node created in a slot that isn't in the nodes
+    fn next_generation(&self, idx: usize) -> u16 {
+        self.generations.get(idx).copied().unwrap_or(0)
+    }
+
     pub fn discard(&mut self, node: NodeId, reuse_data: impl Fn(&mut T)) -> FlagVec {
         self.detach(node);
 
         let ids = FlagVec::with_size(self.nodes.len());
         let ids = self.iter_mut_from(node).fold(ids, |tree, mut ids, id| {
             tree.nodes[id.index()].reuse();
-            tree.availability.set_available(id);
+            if !tree.nodes[id.index()].is_retired() {
+                tree.availability.set_available(id);
+            }
             reuse_data(&mut tree.nodes[id.index()].data);
             ids.set(id.index());
             ids
         });
-        self.nodes[node.index()].reuse();
-        self.availability.set_available(node);
+        // the node itself is the last one iterated
+        debug_assert!(
+            ids.get(node.index()),
+            "BUG: node {node:?} was not discarded"
+        );
         ids
     }
 }
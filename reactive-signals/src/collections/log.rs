@@ -0,0 +1,68 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+/// The version of the collection that a reader has read. `None` until the first read.
+pub(crate) type Seen = Cell<Option<u64>>;
+
+/// The changes made to a collection that haven't yet been read by all of its readers.
+pub(crate) struct ChangeLog<D> {
+    version: u64,
+    /// The changes with the version they produced
+    changes: Vec<(u64, D)>,
+    readers: RefCell<Vec<Weak<Seen>>>,
+}
+
+impl<D> Default for ChangeLog<D> {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            changes: Vec::new(),
+            readers: Default::default(),
+        }
+    }
+}
+
+impl<D: Clone> ChangeLog<D> {
+    pub(crate) fn record(&mut self, diff: D) {
+        self.version += 1;
+        self.changes.push((self.version, diff));
+
+        let readers = self.readers.get_mut();
+        readers.retain(|r| r.strong_count() > 0);
+        let oldest = readers
+            .iter()
+            .filter_map(|r| r.upgrade().and_then(|seen| seen.get()))
+            .min();
+        match oldest {
+            Some(oldest) => self.changes.retain(|(version, _)| *version > oldest),
+            None => self.changes.clear(),
+        }
+    }
+
+    pub(crate) fn add_reader(&self) -> Rc<Seen> {
+        let seen = Rc::new(Cell::new(None));
+        self.readers.borrow_mut().push(Rc::downgrade(&seen));
+        seen
+    }
+
+    /// The changes made since the reader's previous read, or `None` if it
+    /// hasn't read before.
+    pub(crate) fn read(&self, seen: &Seen) -> Option<Vec<D>> {
+        let diff = seen.get().map(|seen| {
+            self.changes
+                .iter()
+                .filter(|(version, _)| *version > seen)
+                .map(|(_, diff)| diff.clone())
+                .collect()
+        });
+        seen.set(Some(self.version));
+        diff
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.changes.len()
+    }
+}
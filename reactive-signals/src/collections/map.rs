@@ -0,0 +1,461 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    hash::Hash,
+    rc::Rc,
+};
+
+use crate::{primitives::AnyData, runtimes::Runtime, signals::Data, Scope, Signal};
+
+use super::{
+    log::{ChangeLog, Seen},
+    update_once,
+};
+
+/// A change made to a [SignalMap].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapDiff<K, V> {
+    Insert { key: K, value: V },
+    Update { key: K, value: V },
+    Remove { key: K },
+    Clear,
+}
+
+struct MapState<K, V, RT: Runtime> {
+    entries: HashMap<K, V>,
+    log: ChangeLog<MapDiff<K, V>>,
+    /// The signals notified when the value of a key changes. Created the first time
+    /// the key is read by a func signal and released once nothing listens to it, when
+    /// the key is removed, the map cleared or the key signals swept.
+    keys: RefCell<HashMap<K, Signal<Data<()>, RT>>>,
+    /// The released key signals, reused for the next keys read. Signals can't be
+    /// discarded on their own, so this keeps their number to at most twice the keys
+    /// listened to at once.
+    free_keys: RefCell<Vec<Signal<Data<()>, RT>>>,
+    /// The number of key signals still listened to after the last sweep. The next
+    /// sweep happens when there are twice as many key signals and none is free.
+    kept_keys: Cell<usize>,
+}
+
+/// A reactive map where a func signal reading a key is only re-run when the
+/// value of that key changes.
+///
+/// Reading one key, with [get()](Self::get()), [with_value()](Self::with_value()) or
+/// [contains_key()](Self::contains_key()), subscribes the running func signal to changes of
+/// that key, including it being inserted or removed. Reading the whole map, with
+/// [with()](Self::with()), [len()](Self::len()) etc. or a [MapReader], subscribes it to
+/// all changes. A [MapReader] gives the [MapDiff]s made since its previous read.
+///
+/// Like a [Signal] it is created in a [Scope] and implements [Copy].
+///
+/// ```rust
+/// use reactive_signals::{collections::SignalMap, runtimes::ClientRuntime, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let users = SignalMap::new(sc, [(1, "kiwi"), (2, "fig")].into());
+///
+/// let greetings = signal!(sc, Vec::<String>::new());
+/// signal!(sc, move || {
+///     let greeting = format!("hi {}", users.get(&1).unwrap_or("nobody"));
+///     greetings.update(|g| g.push(greeting.clone()));
+/// });
+///
+/// // the func signal only reads the user with id 1
+/// users.insert(2, "apple");
+/// users.insert(1, "melon");
+///
+/// assert_eq!(greetings.cloned(), vec!["hi kiwi", "hi melon"]);
+/// ```
+pub struct SignalMap<K: 'static, V: 'static, RT: Runtime + 'static> {
+    sig: Signal<Data<MapState<K, V, RT>>, RT>,
+    /// The scope of the key signals
+    keys_sx: Scope<RT>,
+}
+
+impl<K, V, RT: Runtime> Clone for SignalMap<K, V, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, RT: Runtime> Copy for SignalMap<K, V, RT> {}
+
+impl<K, V, RT> SignalMap<K, V, RT>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + 'static,
+    RT: Runtime + 'static,
+{
+    pub fn new(sx: Scope<RT>, entries: HashMap<K, V>) -> Self {
+        let state: MapState<K, V, RT> = MapState {
+            entries,
+            log: ChangeLog::default(),
+            keys: Default::default(),
+            free_keys: Default::default(),
+            kept_keys: Cell::new(0),
+        };
+        Self {
+            sig: Signal::data(sx, AnyData::new(Data(state))),
+            // the key signals are created in a scope of their own because the
+            // signals of a scope can't be added to while one of them is running
+            keys_sx: sx.new_child(),
+        }
+    }
+
+    /// Creates a reader that gives the changes made since its previous read.
+    pub fn reader(&self) -> MapReader<K, V, RT> {
+        let seen = self.sig.with_untracked(|state| state.log.add_reader());
+        MapReader { map: *self, seen }
+    }
+
+    /// Subscribes the running func signal, if any, to changes of the key.
+    fn track_key(&self, key: &K) {
+        let sx = self.sig.scope();
+        if sx.rt.with_ref(|rt| rt.get_running_signal().is_none()) {
+            return;
+        }
+        let existing = self
+            .sig
+            .with_untracked(|state| state.keys.borrow().get(key).copied());
+        let key_sig = existing.unwrap_or_else(|| self.new_key(key));
+        key_sig.with(|_| ());
+    }
+
+    /// Gives the key a signal, reusing a released one if any. When none is free, the
+    /// signals of the keys that nothing listens to anymore, such as absent keys read by
+    /// discarded func signals, are released first if their number has doubled since the
+    /// last sweep. This keeps the cost of the sweeps proportional to the keys read.
+    fn new_key(&self, key: &K) -> Signal<Data<()>, RT> {
+        let sweep = self.sig.with_untracked(|state| {
+            state.free_keys.borrow().is_empty()
+                && state.keys.borrow().len() >= 2 * state.kept_keys.get()
+        });
+        if sweep {
+            self.release_keys(None);
+            self.sig
+                .with_untracked(|state| state.kept_keys.set(state.keys.borrow().len()));
+        }
+        let free = self
+            .sig
+            .with_untracked(|state| state.free_keys.borrow_mut().pop());
+        let key_sig = free.unwrap_or_else(|| Signal::data(self.keys_sx, AnyData::new(Data(()))));
+        self.sig
+            .with_untracked(|state| state.keys.borrow_mut().insert(key.clone(), key_sig));
+        key_sig
+    }
+
+    /// Applies the change to the key and notifies the subscribers of the map
+    /// and those of the key.
+    fn change<R: 'static>(
+        &self,
+        key: Option<&K>,
+        f: impl FnOnce(&mut MapState<K, V, RT>) -> R,
+    ) -> R {
+        self.sig.scope().batch(|| {
+            let (r, notify) = update_once(&self.sig, |state| {
+                let r = f(state);
+                let keys = state.keys.get_mut();
+                let notify = match key {
+                    Some(key) => keys.get(key).into_iter().copied().collect(),
+                    None => keys.values().copied().collect::<Vec<_>>(),
+                };
+                (r, notify)
+            });
+            notify.iter().for_each(|key_sig| key_sig.set(()));
+            r
+        })
+    }
+
+    /// Releases the signal of the key, or of all keys, that no func signal listens
+    /// to anymore. The listeners of a removed key that still read it are subscribed
+    /// again by the time the change has been propagated.
+    fn release_keys(&self, key: Option<&K>) {
+        self.sig.with_untracked(|state| {
+            let mut keys = state.keys.borrow_mut();
+            let unlistened = (keys.iter())
+                .filter(|(k, sig)| key.is_none_or(|key| key == *k) && !sig.has_listeners())
+                .map(|(k, _)| k.clone())
+                .collect::<Vec<_>>();
+            let released = unlistened.iter().filter_map(|k| keys.remove(k));
+            state.free_keys.borrow_mut().extend(released);
+        })
+    }
+
+    /// Inserts the value and returns the previous value of the key, if any.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let changed = key.clone();
+        self.change(Some(&changed), |state| {
+            let old = state.entries.insert(key.clone(), value.clone());
+            let diff = match old {
+                Some(_) => MapDiff::Update { key, value },
+                None => MapDiff::Insert { key, value },
+            };
+            state.log.record(diff);
+            old
+        })
+    }
+
+    /// Applies the function to the value of the key and returns what it returns,
+    /// or `None` without notifying anyone if the map doesn't contain the key.
+    pub fn update<R: 'static>(&self, key: &K, f: impl FnOnce(&mut V) -> R) -> Option<R> {
+        if !self
+            .sig
+            .with_untracked(|state| state.entries.contains_key(key))
+        {
+            return None;
+        }
+        self.change(Some(key), |state| {
+            let value = state.entries.get_mut(key)?;
+            let r = f(value);
+            let value = value.clone();
+            state.log.record(MapDiff::Update {
+                key: key.clone(),
+                value,
+            });
+            Some(r)
+        })
+    }
+
+    /// Removes the key and returns its value, or `None` without notifying anyone
+    /// if the map doesn't contain the key.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        if !self
+            .sig
+            .with_untracked(|state| state.entries.contains_key(key))
+        {
+            return None;
+        }
+        let value = self.change(Some(key), |state| {
+            let value = state.entries.remove(key)?;
+            state.log.record(MapDiff::Remove { key: key.clone() });
+            Some(value)
+        });
+        self.release_keys(Some(key));
+        value
+    }
+
+    pub fn clear(&self) {
+        self.change(None, |state| {
+            state.entries.clear();
+            state.log.record(MapDiff::Clear);
+        });
+        self.release_keys(None);
+    }
+
+    /// Gives access to the value of the key and subscribes the running func signal
+    /// to changes of that key.
+    pub fn with_value<R: 'static>(&self, key: &K, f: impl Fn(Option<&V>) -> R) -> R {
+        self.track_key(key);
+        self.sig.with_untracked(|state| f(state.entries.get(key)))
+    }
+
+    /// A clone of the value of the key. Subscribes the running func signal
+    /// to changes of that key.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.with_value(key, |value| value.cloned())
+    }
+
+    /// Subscribes the running func signal to changes of the key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.with_value(key, |value| value.is_some())
+    }
+
+    /// Gives access to the whole map and subscribes the running func signal to all changes.
+    pub fn with<R: 'static>(&self, f: impl Fn(&HashMap<K, V>) -> R) -> R {
+        self.sig.with(|state| f(&state.entries))
+    }
+
+    pub fn cloned(&self) -> HashMap<K, V> {
+        self.with(|entries| entries.clone())
+    }
+
+    pub fn keys(&self) -> Vec<K> {
+        self.with(|entries| entries.keys().cloned().collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with(|entries| entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Reads the changes made to a [SignalMap] since its previous read. The first read
+/// gives the entries that the map contains as inserts.
+///
+/// Created with [SignalMap::reader]. Each subscriber should have a reader of its own.
+pub struct MapReader<K: 'static, V: 'static, RT: Runtime + 'static> {
+    map: SignalMap<K, V, RT>,
+    seen: Rc<Seen>,
+}
+
+impl<K, V, RT> MapReader<K, V, RT>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + 'static,
+    RT: Runtime + 'static,
+{
+    /// The changes made since the previous read. Subscribes the running
+    /// func signal to all changes.
+    pub fn diff(&self) -> Vec<MapDiff<K, V>> {
+        self.map.sig.with(|state| {
+            state.log.read(&self.seen).unwrap_or_else(|| {
+                let entries = state.entries.iter();
+                entries
+                    .map(|(key, value)| MapDiff::Insert {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect()
+            })
+        })
+    }
+}
+
+#[test]
+fn test_map_key_subscriptions() {
+    use crate::{runtimes::ServerRuntime, signal, tests::StringStore};
+
+    let sc = ServerRuntime::new_root_scope();
+    let map = SignalMap::new(sc, HashMap::from([(1, 10), (2, 20)]));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(format!("one: {:?}", map.get(&1))));
+    signal!(sc, clone: output, move || output.push(format!("three: {}", map.contains_key(&3))));
+    signal!(sc, clone: output, move || output.push(format!("len: {}", map.len())));
+    assert_eq!(output.values(), "one: Some(10), three: false, len: 2");
+
+    map.update(&2, |v| *v += 1);
+    assert_eq!(
+        output.values(),
+        "one: Some(10), three: false, len: 2, len: 2"
+    );
+
+    assert_eq!(map.insert(1, 11), Some(10));
+    map.insert(3, 30);
+    assert_eq!(map.remove(&4), None);
+    assert_eq!(
+        output.values(),
+        "one: Some(10), three: false, len: 2, len: 2, \
+        one: Some(11), len: 2, three: true, len: 3"
+    );
+
+    // the keys are notified in any order
+    map.clear();
+    let values = output.values();
+    let mut cleared = values.split(", ").skip(8).collect::<Vec<_>>();
+    cleared.sort();
+    assert_eq!(cleared, vec!["len: 0", "one: None", "three: false"]);
+}
+
+#[test]
+fn test_map_diff() {
+    use crate::runtimes::ServerRuntime;
+
+    let sc = ServerRuntime::new_root_scope();
+    let map = SignalMap::new(sc, HashMap::from([("a", 1)]));
+
+    let reader = map.reader();
+    assert_eq!(reader.diff(), vec![MapDiff::Insert { key: "a", value: 1 }]);
+
+    map.insert("b", 2);
+    map.update(&"a", |v| *v = 3);
+    map.remove(&"b");
+    map.clear();
+    assert_eq!(
+        reader.diff(),
+        vec![
+            MapDiff::Insert { key: "b", value: 2 },
+            MapDiff::Update { key: "a", value: 3 },
+            MapDiff::Remove { key: "b" },
+            MapDiff::Clear,
+        ]
+    );
+    assert_eq!(reader.diff(), vec![]);
+}
+
+#[test]
+fn test_map_key_churn() {
+    use crate::{runtimes::ServerRuntime, signal};
+
+    let root = ServerRuntime::new_root_scope();
+    let map = SignalMap::new(root, HashMap::new());
+    let key_count = || map.sig.with_untracked(|state| state.keys.borrow().len());
+    let key_signals = || {
+        root.rt
+            .with_ref(|rt| rt.scope_tree[map.keys_sx.sx].vec_ref().len())
+    };
+
+    let reads = Rc::new(std::cell::Cell::new(0));
+    let sc = root.new_child();
+    let kept = signal!(sc, clone: reads, move || {
+        reads.set(reads.get() + 1);
+        map.get(&0)
+    });
+
+    for key in 1..100 {
+        let sc = root.new_child();
+        signal!(sc, move || map.get(&key));
+        map.insert(key, key);
+        sc.discard();
+        map.remove(&key);
+        assert_eq!(key_count(), 1);
+    }
+    // the signal of each removed key is reused for the next one
+    assert_eq!(key_signals(), 2);
+
+    // the key of a listener that still reads it is kept
+    map.insert(0, 0);
+    map.remove(&0);
+    assert_eq!((kept.get(), reads.get()), (None, 3));
+    assert_eq!(key_count(), 1);
+
+    sc.discard();
+    map.clear();
+    assert_eq!(key_count(), 0);
+    assert_eq!(key_signals(), 2);
+}
+
+#[test]
+fn test_map_absent_keys() {
+    use crate::{runtimes::ServerRuntime, signal};
+
+    let root = ServerRuntime::new_root_scope();
+    let map = SignalMap::<usize, usize, _>::new(root, HashMap::new());
+    let key_signals = || {
+        root.rt
+            .with_ref(|rt| rt.scope_tree[map.keys_sx.sx].vec_ref().len())
+    };
+
+    // readers of absent keys that are discarded one after the other
+    for key in 0..100 {
+        let sc = root.new_child();
+        signal!(sc, move || map.get(&key));
+        sc.discard();
+    }
+    assert_eq!(key_signals(), 1);
+
+    // readers of absent keys that are discarded together
+    let sc = root.new_child();
+    for key in 100..150 {
+        signal!(sc, move || map.get(&key));
+    }
+    sc.discard();
+    assert_eq!(key_signals(), 50);
+
+    // the unlistened key signals are swept before there are twice as many
+    let read_sequentially = |keys: std::ops::Range<usize>| {
+        for key in keys {
+            let sc = root.new_child();
+            signal!(sc, move || map.contains_key(&key));
+            sc.discard();
+        }
+    };
+    read_sequentially(150..300);
+    let swept = key_signals();
+    assert!(swept <= 100);
+    read_sequentially(300..1000);
+    assert_eq!(key_signals(), swept);
+}
@@ -0,0 +1,25 @@
+//!
+//! Reactive collections that record the changes made to them
+//!
+mod log;
+mod map;
+mod vec;
+
+use std::cell::Cell;
+
+use crate::{runtimes::Runtime, signals::Data, Signal};
+
+pub use map::{MapDiff, MapReader, SignalMap};
+pub use vec::{SignalVec, VecDiff, VecReader};
+
+/// Same as [Signal::update] but for a function that can only be called once.
+fn update_once<T, RT, R>(sig: &Signal<Data<T>, RT>, f: impl FnOnce(&mut T) -> R) -> R
+where
+    T: 'static,
+    RT: Runtime,
+    R: 'static,
+{
+    // update takes an Fn, but is only calling it once
+    let f = Cell::new(Some(f));
+    sig.update(|val| (f.take().unwrap())(val))
+}
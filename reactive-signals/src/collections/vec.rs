@@ -0,0 +1,280 @@
+use std::rc::Rc;
+
+use crate::{primitives::AnyData, runtimes::Runtime, signals::Data, Scope, Signal};
+
+use super::{
+    log::{ChangeLog, Seen},
+    update_once,
+};
+
+/// A change made to a [SignalVec].
+///
+/// Applying the changes, in order, to a copy of the vec makes it equal to the [SignalVec].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    Insert { index: usize, value: T },
+    Remove { index: usize },
+    Move { from: usize, to: usize },
+    Replace { index: usize, value: T },
+    Clear,
+}
+
+struct VecState<T> {
+    items: Vec<T>,
+    log: ChangeLog<VecDiff<T>>,
+}
+
+/// A reactive vec that records the changes made to it, so that subscribers can update
+/// what they have built from it instead of rebuilding it when it changes.
+///
+/// Reading it from a func signal, with [with()](Self::with()) or a [VecReader], subscribes
+/// the func signal to all changes. A [VecReader] gives the [VecDiff]s made since its
+/// previous read.
+///
+/// Like a [Signal] it is created in a [Scope] and implements [Copy].
+///
+/// ```rust
+/// use reactive_signals::{collections::{SignalVec, VecDiff}, runtimes::ClientRuntime, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let list = SignalVec::new(sc, vec!["kiwi"]);
+/// let log = signal!(sc, Vec::<VecDiff<&str>>::new());
+///
+/// let reader = list.reader();
+/// signal!(sc, move || {
+///     let diff = reader.diff();
+///     log.update(|l| l.extend(diff.iter().cloned()));
+/// });
+///
+/// list.push("fig");
+/// list.move_item(1, 0);
+///
+/// assert_eq!(list.cloned(), vec!["fig", "kiwi"]);
+/// assert_eq!(log.cloned(), vec![
+///     VecDiff::Insert { index: 0, value: "kiwi" },
+///     VecDiff::Insert { index: 1, value: "fig" },
+///     VecDiff::Move { from: 1, to: 0 },
+/// ]);
+/// ```
+pub struct SignalVec<T: 'static, RT: Runtime> {
+    sig: Signal<Data<VecState<T>>, RT>,
+}
+
+impl<T, RT: Runtime> Clone for SignalVec<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, RT: Runtime> Copy for SignalVec<T, RT> {}
+
+impl<T: Clone + 'static, RT: Runtime> SignalVec<T, RT> {
+    pub fn new(sx: Scope<RT>, items: Vec<T>) -> Self {
+        let state = VecState {
+            items,
+            log: ChangeLog::default(),
+        };
+        Self {
+            sig: Signal::data(sx, AnyData::new(Data(state))),
+        }
+    }
+
+    /// Creates a reader that gives the changes made since its previous read.
+    pub fn reader(&self) -> VecReader<T, RT> {
+        let seen = self.sig.with_untracked(|state| state.log.add_reader());
+        VecReader { list: *self, seen }
+    }
+
+    /// Applies the change and notifies the subscribers.
+    fn change<R: 'static>(&self, f: impl FnOnce(&mut VecState<T>) -> R) -> R {
+        update_once(&self.sig, f)
+    }
+
+    pub fn push(&self, value: T) {
+        self.change(|state| {
+            let index = state.items.len();
+            state.items.push(value.clone());
+            state.log.record(VecDiff::Insert { index, value });
+        })
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        self.change(|state| {
+            let value = state.items.pop()?;
+            let index = state.items.len();
+            state.log.record(VecDiff::Remove { index });
+            Some(value)
+        })
+    }
+
+    /// Panics if `index > len`.
+    pub fn insert(&self, index: usize, value: T) {
+        self.change(|state| {
+            state.items.insert(index, value.clone());
+            state.log.record(VecDiff::Insert { index, value });
+        })
+    }
+
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        self.change(|state| {
+            let value = state.items.remove(index);
+            state.log.record(VecDiff::Remove { index });
+            value
+        })
+    }
+
+    /// Moves the item at `from` so that it ends up at `to`.
+    /// Panics if either of them is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.change(|state| {
+            let value = state.items.remove(from);
+            state.items.insert(to, value);
+            state.log.record(VecDiff::Move { from, to });
+        })
+    }
+
+    /// Replaces the item at `index` and returns the previous one.
+    /// Panics if `index` is out of bounds.
+    pub fn replace(&self, index: usize, value: T) -> T {
+        self.change(|state| {
+            let old = std::mem::replace(&mut state.items[index], value.clone());
+            state.log.record(VecDiff::Replace { index, value });
+            old
+        })
+    }
+
+    pub fn clear(&self) {
+        self.change(|state| {
+            state.items.clear();
+            state.log.record(VecDiff::Clear);
+        })
+    }
+
+    /// Gives access to the items and subscribes the running func signal to all changes.
+    pub fn with<R: 'static>(&self, f: impl Fn(&[T]) -> R) -> R {
+        self.sig.with(|state| f(&state.items))
+    }
+
+    pub fn cloned(&self) -> Vec<T> {
+        self.with(|items| items.to_vec())
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.with(|items| items.get(index).cloned())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with(|items| items.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Reads the changes made to a [SignalVec] since its previous read. The first read
+/// gives the items that the vec contains as inserts.
+///
+/// Created with [SignalVec::reader]. Each subscriber should have a reader of its own.
+pub struct VecReader<T: 'static, RT: Runtime> {
+    list: SignalVec<T, RT>,
+    seen: Rc<Seen>,
+}
+
+impl<T: Clone + 'static, RT: Runtime> VecReader<T, RT> {
+    /// The changes made since the previous read. Subscribes the running
+    /// func signal to all changes.
+    pub fn diff(&self) -> Vec<VecDiff<T>> {
+        self.list.sig.with(|state| {
+            state.log.read(&self.seen).unwrap_or_else(|| {
+                let items = state.items.iter().cloned().enumerate();
+                items
+                    .map(|(index, value)| VecDiff::Insert { index, value })
+                    .collect()
+            })
+        })
+    }
+}
+
+#[test]
+fn test_vec_diff() {
+    use crate::{runtimes::ServerRuntime, signal};
+
+    let sc = ServerRuntime::new_root_scope();
+    let list = SignalVec::new(sc, vec![1, 2, 3]);
+
+    let replay = signal!(sc, Vec::<i32>::new());
+    let reader = list.reader();
+    signal!(sc, move || {
+        let diff = reader.diff();
+        replay.update(|items| {
+            diff.iter().cloned().for_each(|d| match d {
+                VecDiff::Insert { index, value } => items.insert(index, value),
+                VecDiff::Remove { index } => drop(items.remove(index)),
+                VecDiff::Move { from, to } => {
+                    let value = items.remove(from);
+                    items.insert(to, value);
+                }
+                VecDiff::Replace { index, value } => items[index] = value,
+                VecDiff::Clear => items.clear(),
+            })
+        });
+    });
+    assert_eq!(replay.cloned(), vec![1, 2, 3]);
+
+    list.push(4);
+    assert_eq!(list.remove(0), 1);
+    list.move_item(2, 0);
+    assert_eq!(list.replace(1, 5), 2);
+    assert_eq!(list.pop(), Some(3));
+    list.insert(1, 6);
+    assert_eq!(list.cloned(), vec![4, 6, 5]);
+    assert_eq!(replay.cloned(), list.cloned());
+
+    // several changes in a batch are given to the reader at once
+    sc.batch(|| {
+        list.clear();
+        list.push(7);
+    });
+    assert_eq!(replay.cloned(), vec![7]);
+}
+
+#[test]
+fn test_vec_readers() {
+    use crate::runtimes::ServerRuntime;
+
+    let sc = ServerRuntime::new_root_scope();
+    let list = SignalVec::new(sc, vec!['a']);
+
+    let first = list.reader();
+    let second = list.reader();
+    assert_eq!(
+        first.diff(),
+        vec![VecDiff::Insert {
+            index: 0,
+            value: 'a'
+        }]
+    );
+
+    list.push('b');
+    assert_eq!(
+        first.diff(),
+        vec![VecDiff::Insert {
+            index: 1,
+            value: 'b'
+        }]
+    );
+    assert_eq!(first.diff(), vec![]);
+    // a reader that hasn't read anything gets the current items
+    assert_eq!(second.diff().len(), 2);
+
+    list.clear();
+    assert_eq!(second.diff(), vec![VecDiff::Clear]);
+    // only the changes that haven't been read by all readers are kept
+    list.sig.with(|state| assert_eq!(state.log.len(), 1));
+
+    drop(first);
+    list.push('c');
+    list.sig.with(|state| assert_eq!(state.log.len(), 1));
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::{runtimes::Runtime, signals::SignalId};
+
+/// A cycle was detected while propagating a signal change. This happens when a
+/// func signal, directly or indirectly, depends on itself. For example when a func
+/// signal sets a data signal that it reads.
+///
+/// The propagation stops at the signal that would have closed the cycle.
+///
+/// In debug builds the infallible functions ([Signal::set](crate::Signal::set),
+/// [Signal::update](crate::Signal::update) etc.) panic with this error, whereas in release
+/// builds the error is only available through the fallible ones
+/// ([Signal::try_set](crate::Signal::try_set), [Signal::try_update](crate::Signal::try_update)),
+/// as a [ReactiveError::Cycle].
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let count = signal!(sc, 0);
+/// // resets the count when it gets too big which is a cycle
+/// // because the signal sets the count that it reads
+/// signal!(sc, move || {
+///     if count.get() > 10 {
+///         let _ = count.try_set(0);
+///     }
+/// });
+///
+/// let err = count.try_set(20).unwrap_err();
+/// assert_eq!(err.to_string(), "signal cycle detected (scope:signal): 0:0 → 0:1 → 0:0");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CycleError {
+    path: Vec<(usize, usize)>,
+}
+
+impl CycleError {
+    pub(crate) fn new<RT: Runtime>(path: &[SignalId<RT>]) -> Self {
+        Self {
+            path: path.iter().map(|id| (id.sx.index(), id.index())).collect(),
+        }
+    }
+
+    /// The signals forming the cycle as (scope index, signal index) pairs.
+    /// The first and last entries are the same signal.
+    pub fn path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self
+            .path
+            .iter()
+            .map(|(sx, id)| format!("{sx}:{id}"))
+            .collect::<Vec<_>>()
+            .join(" → ");
+        write!(f, "signal cycle detected (scope:signal): {path}")
+    }
+}
+
+impl fmt::Debug for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// The error returned by the fallible [Signal](crate::Signal) accessors such as
+/// [try_get](crate::Signal::try_get) and [try_set](crate::Signal::try_set).
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal, ReactiveError};
+///
+/// let root = ClientRuntime::new_root_scope();
+/// let sc = root.new_child();
+/// let count = signal!(sc, 1);
+///
+/// sc.discard();
+/// assert_eq!(count.try_get(), Err(ReactiveError::DiscardedScope));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ReactiveError {
+    /// The signal belongs to a scope that has been discarded
+    DiscardedScope,
+    /// The signal belongs to a runtime that has been discarded
+    DiscardedRuntime,
+    /// The signal's value or the runtime is already borrowed. For example when
+    /// setting a signal from inside a `.with()` closure of the same signal.
+    ///
+    /// Not detected when using the `unsafe-cell` feature.
+    ReentrantBorrow,
+    /// The signal belongs to a runtime that doesn't exist on the current thread
+    WrongRuntime,
+    /// A cycle was detected while propagating the change
+    Cycle(CycleError),
+}
+
+impl fmt::Display for ReactiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DiscardedScope => write!(f, "the scope has been discarded"),
+            Self::DiscardedRuntime => write!(f, "the runtime has been discarded"),
+            Self::ReentrantBorrow => write!(f, "the value is already borrowed"),
+            Self::WrongRuntime => write!(f, "the runtime doesn't exist on this thread"),
+            Self::Cycle(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for ReactiveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cycle(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<CycleError> for ReactiveError {
+    fn from(error: CycleError) -> Self {
+        Self::Cycle(error)
+    }
+}
@@ -28,10 +28,6 @@ impl<V: IdVec> IdVecIter<V> {
             self.len - self.pos - 1
         }
     }
-
-    pub(crate) fn has_more(&self) -> bool {
-        self.len - 1 > self.pos
-    }
 }
 
 impl<V: IdVec> Iterator for IdVecIter<V> {
@@ -92,20 +88,16 @@ fn test_id_vec() {
     let val = iter.next();
     assert_eq!(val, Some(0));
     assert_eq!(iter.remaining(), 2);
-    assert_eq!(iter.has_more(), true);
 
     let val = iter.next();
     assert_eq!(val, Some(1));
-    assert_eq!(iter.has_more(), true);
     assert_eq!(iter.remaining(), 1);
 
     let val = iter.next();
     assert_eq!(val, Some(2));
-    assert_eq!(iter.has_more(), false);
     assert_eq!(iter.remaining(), 0);
 
     let val = iter.next();
     assert_eq!(val, None);
-    assert_eq!(iter.has_more(), false);
     assert_eq!(iter.remaining(), 0);
 }
@@ -22,14 +22,22 @@
 //! - [Signal](crate::signals::Signal)s produce a reactive value, for data signals, it's the inner data and for functional signals,
 //!   it's the value produced by the function. Subscribers are notified when the value is updated,
 //!   or for a value that implements [PartialEq](::std::cmp::PartialEq), when it is changed.
-//! - Type-safe attached data to scopes. See the [Scope](crate::scope::scope::Scope) doc.<sup>TBD</sup>
+//! - Fine-grained reactive structs, with a signal for each field, using `#[derive(Store)]`.
+//!   See [Store](crate::Store).
+//! - Type-safe attached data to scopes. See the [Scope](crate::scope::scope::Scope) doc.
 //! - 4 times less memory overhead and 3.5 times faster (worst case) than [leptos_reactive](https://crates.io/crates/leptos_reactive).
 //!   See [Benchmarks](Self#Benchmarks) below.
 //! - Push-pull updates: Guarantees that the nodes are only updated once and only if necessary.
-//!   See the end of the [reactively](https://github.com/modderme123/reactively) readme for more information.<sup>TBC</sup>
-//! - Tokio [tracing](https://crates.io/crates/tracing) compatibility.<sup>TBC</sup>
-//! - async signals with runtimes using a custom async runtime when running in a web browser and
-//!   [tokio](https://crates.io/crates/tokio) when running in a server. See the [signal!](crate::macros::signal) doc.<sup>TBC</sup>
+//!   See the end of the [reactively](https://github.com/modderme123/reactively) readme for more information.
+//! - Reactive collections that record their changes, so that lists can be updated instead of rebuilt.
+//!   See [SignalVec](crate::collections::SignalVec) and [SignalMap](crate::collections::SignalMap).
+//! - Tokio [tracing](https://crates.io/crates/tracing) compatibility with the `tracing` feature.
+//! - Async and timer signals, with their futures polled by a single-threaded executor owned by the runtime.
+//!   See the [signal!](crate::macros::signal) doc, and [Resource](crate::Resource) for fetching data.
+//! - Polled signals: the runtime collects the changes of the signals registered for polling, so that for
+//!   instance DOM updates can be grouped into one update per frame. See [Signal::register_polled](crate::Signal::register_polled).
+//! - State snapshots of the scopes and signals, with their subscriptions and values, recorded after each outside
+//!   change, compared with each other and exported as DOT or Mermaid graphs, with the `snapshots` feature.
 //! - Mirror the leptos_reactive API with deprecations that give instructions on how to upgrade
 //!   to give a smooth upgrade experience. If there's interest, of course.<sup>TBC</sup>
 //! - Production-class test-coverage.<sup>TBC</sup>
@@ -44,19 +52,20 @@
 //! - `unsafe-cell`: Internally, the reactive-signals use [RefCell](::core::cell::RefCell) for interior mutability.
 //!   Once reactive-signals is mature and if your app is well tested, then [UnsafeCell](::core::cell::UnsafeCell)
 //!   can be used, resulting in a performance improvement of around 40% and a reduction in memory use by some 20%.
+//! - `large-ids`: Scopes and signals are identified with u16 indexes, limiting a runtime to 65k scopes
+//!   with 32k signals each. This feature uses u32 indexes instead, at the cost of larger signals.
+//! - `tracing`: Emits [tracing](https://crates.io/crates/tracing) spans for each change propagation and each
+//!   func signal run, with the id of the signal, and events when scopes are created and discarded.
+//! - `snapshots`: Records snapshots of the scopes and signals of a runtime, with `Runtime::snapshot()` and
+//!   `Runtime::record_snapshots()`, and exports them as DOT or Mermaid graphs. Without it, creating and
+//!   changing signals doesn't do any of the bookkeeping needed for the snapshots.
 //!
 //!
 //! # Evolutions
 //!
-//! - **Timetravel**. Due to how reactive-signals is structured it is possible to create state snapshots that can
-//!   be used to create a real-time visualization of the signals, grouped by their
-//!   scope with edges between connected signals. Each outside action or event would trigger
-//!   a new state snapshot. A state snapshot would be visualized by highlighting the
-//!   triggering signal and all its dependencies recursively. When the signal's value implements Debug or Display
-//!   it can be used to visualize its content.
-//! - **Polled signals**. Option to register signals for polling so that a runtime vec will contain all changed signals
-//!   since the last polling. This could be used to group DOM updates into one update per frame avoiding
-//!   the overhead of many small and costly calls out of the WASM. The usefulness of it for Leptos would need to be investigated.
+//! - **Timetravel**. The recorded state snapshots can be used to create a real-time visualization of the signals,
+//!   grouped by their scope with edges between connected signals. A state snapshot would be visualized by highlighting
+//!   the triggering signal and all its dependencies recursively, with the content of the values that implement Debug.
 //! - **Remote shim** (speculative). Everything that goes in or out of a WASM is converted between Rust and JS data.
 //!   It should be possible to put a shim in that serializes it to a remote app. Why do such a thing? It would help to make
 //!   full hot-reloading possible and to apply various tricks for greatly speeding up the compile times.
@@ -125,19 +134,38 @@
 pub mod tests;
 
 mod arena_tree;
+pub mod collections;
+mod error;
 mod iter;
 mod macros;
 mod primitives;
+mod resource;
 pub mod runtimes;
 mod scope;
 mod signals;
+#[doc(hidden)]
+pub mod store;
+
+// makes the paths generated by the derive macros work inside this crate
+extern crate self as reactive_signals;
 
 #[doc(hidden)]
 pub use arena_tree::{Node, Tree};
-pub use scope::Scope;
+pub use error::{CycleError, ReactiveError};
+pub use resource::Resource;
+#[doc(hidden)]
+pub use scope::Attached;
+pub use scope::{BatchGuard, Scope};
 #[doc(hidden)]
 pub use signals::kinds::*;
-pub use signals::Signal;
+pub use signals::{Signal, SignalId};
+pub use store::Store;
+
+/// Derives a [Store](trait@Store), see its documentation.
+pub use reactive_signals_macros::Store;
+
+/// Attaches a value to a scope, see the [Scope] documentation.
+pub use reactive_signals_macros::Scoped;
 
 use runtimes::Runtime;
 use scope::ScopeInner;
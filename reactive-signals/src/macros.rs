@@ -5,7 +5,14 @@
 /// - `scope`: mandatory. The [Scope](crate::Scope) to use when creating the [Signal](crate::Signal)
 /// - `clone:`: optional. A space-separated list of data to clone and provide to the function.
 /// - `server` | `client`: optional. Whether the signal should run only on the server or the client.
-/// - `inner`: the data or function the signal handles.
+/// - `lazy`: optional. The function is only run when the signal is read.
+/// - `inner`: the data or function the signal handles. A function taking an `Option<&T>`
+///   argument is given the value it returned last time. An async closure, written as
+///   `move async || ..`, creates an async func signal.
+///
+/// Instead of the arguments, it takes the result of a combinator, such as
+/// `signal!(count.map(|c| c * 2))`, to create a func signal in the scope of the signal
+/// the combinator is called on. See [Signal::map()](crate::Signal::map()).
 ///
 /// Examples:
 ///
@@ -70,80 +77,265 @@
 ///
 /// // create a signal that only runs on the client
 /// let client_func = signal!(sc, client, move || count_sig.get() + 1);
+///
+/// ///////////// lazy signals /////////////
+///
+/// // create a signal that is computed when read and
+/// // recomputed only when read again after a change
+/// let lazy_func = signal!(sc, lazy, move || count_sig.get() * 2);
+/// assert_eq!(lazy_func.get(), 8);
+///
+/// ///////////// the previous value /////////////
+///
+/// // a function with a `prev` argument receives the value it returned last
+/// // time, which is `None` on the first run
+/// let count_sum = signal!(sc, move |prev: Option<&i32>| prev.unwrap_or(&0) + count_sig.get());
+/// count_sig.set(5);
+/// assert_eq!(count_sum.get(), 9);
 /// ```
 ///
 /// # Example of async functional reactive signals
 ///
-/// Note that this has not yet been implemented and the exact details of the API
-/// has not been ironed out.
+/// An async func signal is created from an async closure, written as `move async || ..`.
+/// Its value is `None` until the first future completes and then the value of the last
+/// completed future. Like a func signal, it runs the closure again when the signals the
+/// closure reads change, which cancels the future that is still running, if any.
+///
+/// The futures are polled by a single-threaded executor owned by the runtime. They are
+/// polled on the thread of the runtime, as soon as they are woken.
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal};
 ///
-/// ```rust ignore
-/// // creating an async closure/function is basically the same as normal one
-/// let remote_count = signal!(sc, move async || {
-///     // some async stuff
-///     fetch_usize_count().await
+/// let sc = ClientRuntime::new_root_scope();
+/// let user_id = signal!(sc, 1);
+///
+/// let user_name = signal!(sc, move async || {
+///     let id = user_id.get();
+///     fetch_user_name(id).await
 /// });
 ///
 /// // an async signal works just like any other signal, it just waits until
-/// // the async closure finishes before notifiying subscribers.
-/// signal!(sc, move || println!("Remote count is now: {}", remote_count.get()));
+/// // the future completes before notifying subscribers.
+/// let greeting = signal!(sc, move || format!("hi {}", user_name.cloned().unwrap_or_default()));
+///
+/// user_id.set(2);
+/// assert_eq!(greeting.cloned(), "hi user 2");
+///
+/// # async fn fetch_user_name(id: u32) -> String {
+/// #     format!("user {id}")
+/// # }
+/// ```
+///
+/// # Example of timer signals
+///
+/// A timer signal runs an async closure after an interval, given in milliseconds, and then
+/// repeatedly until the closure sets the interval, which it receives as argument, to `None`.
+/// Its value is the one returned by the closure the last time it ran. The closure can change
+/// the interval for the next run, and a timeout is a closure that sets it to `None`. An
+/// interval shorter than 1 ms, such as zero, is run as 1 ms.
 ///
-/// let async_timer = signal!(sc, 500 ms, move async |&mut interval| {
-///     // runs after 500 ms
-///     // you can change the interval or stop it by:
-///     *interval = None;
+/// Like an async func signal, the timer starts over when the signals the closure reads change.
+/// Timer signals only run on the client and with a [ClientRuntime](crate::runtimes::ClientRuntime)
+/// they are run by [run_timers()](crate::runtimes::ClientRuntime::run_timers()). A
+/// [TestClientRuntime](crate::runtimes::TestClientRuntime) has a virtual clock that is
+/// advanced with [advance()](crate::runtimes::TestClientRuntime::advance()).
+///
+/// ```rust
+/// use reactive_signals::{runtimes::TestClientRuntime, signal};
+/// use std::time::Duration;
+///
+/// let sc = TestClientRuntime::new_root_scope();
+/// let remaining = signal!(sc, 3);
+///
+/// // runs after 500 ms and then every 100 ms until nothing remains
+/// let countdown = signal!(sc, 500 ms, move async |interval| {
+///     let left = remaining.untracked_get() - 1;
+///     *interval = (left > 0).then(|| Duration::from_millis(100));
+///     remaining.set(left);
+///     left
 /// });
+///
+/// sc.runtime().advance(Duration::from_millis(500));
+/// assert_eq!(countdown.get(), Some(2));
+///
+/// sc.runtime().advance(Duration::from_secs(1));
+/// assert_eq!(countdown.get(), Some(0));
 /// ```
+///
 #[macro_export]
 macro_rules! signal {
+    ($scope:ident, $ms:literal ms, move async |$interval:ident| $body:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{EqTimerKind, TrueTimerKind};
+        match ($crate::Scope::from($scope), async move |$interval: &mut Option<::std::time::Duration>| $body) {
+            tuple => {
+                let signal = (&&tuple).timer_kind().new(tuple, ::std::time::Duration::from_millis($ms));
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
+    ($scope:ident, clone: $($clone:ident) +, $ms:literal ms, move async |$interval:ident| $body:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{EqTimerKind, TrueTimerKind};
+        match ($crate::Scope::from($scope), async move |$interval: &mut Option<::std::time::Duration>| $body) {
+            tuple => {
+                let signal = (&&tuple).timer_kind().new(tuple, ::std::time::Duration::from_millis($ms));
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
+    ($scope:ident, move async || $body:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{EqAsyncFuncKind, TrueAsyncFuncKind};
+        match ($crate::Scope::from($scope), async move || $body) {
+            tuple => {
+                let signal = (&&tuple).async_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
+    ($scope:ident, clone: $($clone:ident) +, move async || $body:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{EqAsyncFuncKind, TrueAsyncFuncKind};
+        match ($crate::Scope::from($scope), async move || $body) {
+            tuple => {
+                let signal = (&&tuple).async_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
     ($scope:ident, $inner:expr) => {{
         #[allow(unused_imports)]
-        use $crate::{EqFuncKind, TrueFuncKind, EqDataKind, TrueDataKind, HashEqDataKind};
-        match ($scope, $inner) {
-            tuple => (&&tuple).signal_kind().new(tuple),
+        use $crate::{EqFuncKind, TrueFuncKind, EqPrevFuncKind, TruePrevFuncKind, EqDataKind, TrueDataKind, HashEqDataKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&&tuple).signal_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
     ($scope:ident, server, $inner:expr) => {{
         #[allow(unused_imports)]
         use $crate::{ServerEqFuncKind, ServerTrueFuncKind};
-        match ($scope, $inner) {
-            tuple => (&&tuple).server_kind().new(tuple),
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).server_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
     ($scope:ident, server, clone: $($clone:ident) +, $inner:expr) => {{
         $(let $clone = $clone.clone();)*
         #[allow(unused_imports)]
         use $crate::{ServerEqFuncKind, ServerTrueFuncKind};
-        match ($scope, $inner) {
-            tuple => (&&tuple).server_kind().new(tuple),
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).server_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
 
     ($scope:ident, client, $inner:expr) => {{
         #[allow(unused_imports)]
         use $crate::{ClientEqFuncKind, ClientTrueFuncKind};
-        match ($scope, $inner) {
-            tuple => (&&tuple).client_kind().new(tuple),
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).client_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
     ($scope:ident, client, clone: $($clone:ident) +, $inner:expr) => {{
         $(let $clone = $clone.clone();)*
         #[allow(unused_imports)]
         use $crate::{ClientEqFuncKind, ClientTrueFuncKind};
-        match ($scope, $inner) {
-            tuple => (&&tuple).client_kind().new(tuple),
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).client_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
+    ($scope:ident, lazy, $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{LazyEqFuncKind, LazyTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).lazy_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};
+    ($scope:ident, lazy, clone: $($clone:ident) +, $inner:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{LazyEqFuncKind, LazyTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => {
+                let signal = (&&tuple).lazy_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
     ($scope:ident, clone: $($clone:ident) +, $data:expr) => {{
         $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{EqFuncKind, TrueFuncKind, EqPrevFuncKind, TruePrevFuncKind};
+        match ($crate::Scope::from($scope), $data) {
+            tuple => {
+                let signal = (&&&tuple).signal_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
+        }
+    }};    ($combinator:expr) => {{
         #[allow(unused_imports)]
         use $crate::{EqFuncKind, TrueFuncKind};
-        match ($scope, $data) {
-            tuple => (&&tuple).signal_kind().new(tuple),
+        match $combinator {
+            tuple => {
+                let signal = (&&&tuple).signal_kind().new(tuple);
+                $crate::__register_debug!(signal);
+                signal
+            }
         }
     }};
 }
 
+/// Registers how to render the value of a signal in the snapshots.
+#[cfg(feature = "snapshots")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_debug {
+    ($signal:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::{DebugValueKind, NoDebugValueKind};
+        (&&$crate::DebugProbe(&$signal)).register_debug();
+    }};
+}
+
+/// Without snapshots there is nothing to register.
+#[cfg(not(feature = "snapshots"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_debug {
+    ($signal:ident) => {};
+}
+
 #[test]
 fn test() {
     use crate::runtimes::ServerRuntime;
@@ -170,6 +362,13 @@ fn test() {
     let ne = NonEq;
     let _sig = signal!(sx, clone: ne, move || ne.clone());
 
+    let _sig = signal!(sx, |prev: Option<&i32>| prev.map_or(0, |p| p + 1));
+
+    let _sig = signal!(sx, |_prev: Option<&NonEq>| NonEq);
+
+    let ne = NonEq;
+    let _sig = signal!(sx, clone: ne, move |_prev: Option<&NonEq>| ne.clone());
+
     // assert!(!sig.eq);
 
     let _sit = signal!(sx, server, move || ne.clone());
@@ -180,4 +379,11 @@ fn test() {
     let clnt = signal!(sx, client, move || 1);
 
     assert_eq!(clnt.opt_get(), None);
+
+    let lazy = signal!(sx, lazy, move || 1);
+    assert_eq!(lazy.get(), 1);
+
+    let ne = NonEq;
+    let lazy = signal!(sx, lazy, clone: ne, move || ne.clone());
+    let _ = lazy.cloned();
 }
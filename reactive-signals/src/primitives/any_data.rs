@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use crate::{signals::SignalType, CellType};
+use crate::{signals::SignalType, CellType, ReactiveError};
 
 pub struct AnyData(pub(crate) Box<CellType<dyn Any>>);
 
@@ -24,6 +24,12 @@ impl AnyData {
         f(val.inner())
     }
 
+    pub fn is_stale<T: SignalType + 'static>(&self) -> bool {
+        let val_any = self.val_ref();
+        let val = (*val_any).downcast_ref::<T>().unwrap();
+        val.is_stale()
+    }
+
     pub fn update<T, R>(&self, f: impl Fn(&mut T::Inner) -> R) -> (bool, R)
     where
         T: SignalType + 'static,
@@ -61,6 +67,14 @@ impl AnyData {
         *val.inner()
     }
 
+    /// Gives access to the untyped value, unless it is mutably borrowed
+    #[cfg(feature = "snapshots")]
+    pub(crate) fn try_with_any<R>(&self, f: impl FnOnce(&dyn Any) -> R) -> Option<R> {
+        self.check_borrow(false).ok()?;
+        let val_any = self.val_ref();
+        Some(f(&*val_any))
+    }
+
     pub fn set<T: SignalType + 'static>(&self, val: T::Inner) -> bool {
         #[allow(unused_mut)]
         let mut val_any = self.val_mut();
@@ -81,6 +95,19 @@ impl AnyData {
     fn val_mut(&self) -> std::cell::RefMut<dyn Any> {
         self.0.borrow_mut()
     }
+
+    /// Checks that the value can be borrowed, mutably or not, without panicking
+    #[inline]
+    pub fn check_borrow(&self, mutable: bool) -> Result<(), ReactiveError> {
+        let borrowable = if mutable {
+            self.0.try_borrow_mut().is_ok()
+        } else {
+            self.0.try_borrow().is_ok()
+        };
+        borrowable
+            .then_some(())
+            .ok_or(ReactiveError::ReentrantBorrow)
+    }
 }
 
 #[cfg(feature = "unsafe-cell")]
@@ -94,4 +121,9 @@ impl AnyData {
     fn val_mut(&self) -> &mut dyn Any {
         unsafe { &mut *self.0.get() }
     }
+
+    #[inline]
+    pub fn check_borrow(&self, _mutable: bool) -> Result<(), ReactiveError> {
+        Ok(())
+    }
 }
@@ -1,13 +1,17 @@
 use std::any::Any;
 
-use crate::{signals::SignalType, CellType};
+use crate::{
+    signals::{types::LazySignalType, SignalType},
+    CellType,
+};
 
 use super::AnyData;
 
 type BoxAnyData = Box<CellType<dyn Any>>;
+type BoxFunc = Box<dyn Fn(&BoxAnyData, bool) -> bool>;
 
 pub struct DynFunc {
-    pub(crate) func: Box<dyn Fn(&BoxAnyData) -> bool>,
+    pub(crate) func: BoxFunc,
     pub(crate) value: AnyData,
 }
 
@@ -24,7 +28,7 @@ impl DynFunc {
         T: 'static,
     {
         let val = AnyData::new(W::new(func()));
-        let func = Box::new(move |val: &BoxAnyData| {
+        let func = Box::new(move |val: &BoxAnyData, _observed: bool| {
             let new = func();
 
             #[cfg(not(feature = "unsafe-cell"))]
@@ -32,14 +36,90 @@ impl DynFunc {
             #[cfg(feature = "unsafe-cell")]
             let old_any: &mut dyn Any = unsafe { &mut *val.get() };
 
-            let old: &mut T = old_any.downcast_mut::<W>().unwrap().inner_mut();
-            *old = new;
-            true
+            let old = old_any.downcast_mut::<W>().unwrap();
+            let eq = old.is_eq(&new);
+            *old.inner_mut() = new;
+            !eq
         });
         Self { func, value: val }
     }
 
-    pub fn run(&self) -> bool {
-        (self.func)(&self.value.0)
+    /// Creates a function that is given the value it returned last time,
+    /// or `None` when it is run for the first time.
+    pub fn new_prev<F, T, W: SignalType<Inner = T>>(func: F) -> Self
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: 'static,
+    {
+        let val = AnyData::new(W::new(func(None)));
+        let func = Box::new(move |val: &BoxAnyData, _observed: bool| {
+            #[cfg(not(feature = "unsafe-cell"))]
+            let new = func(Some(val.borrow().downcast_ref::<W>().unwrap().inner()));
+            #[cfg(feature = "unsafe-cell")]
+            let new = func(Some(
+                unsafe { &*val.get() }.downcast_ref::<W>().unwrap().inner(),
+            ));
+
+            #[cfg(not(feature = "unsafe-cell"))]
+            let mut old_any = val.borrow_mut();
+            #[cfg(feature = "unsafe-cell")]
+            let old_any: &mut dyn Any = unsafe { &mut *val.get() };
+
+            let old = old_any.downcast_mut::<W>().unwrap();
+            let eq = old.is_eq(&new);
+            *old.inner_mut() = new;
+            !eq
+        });
+        Self { func, value: val }
+    }
+
+    /// Creates a function that isn't run until the value is read.
+    ///
+    /// When run while not `observed` by any other signal, the value is
+    /// discarded instead of recomputed.
+    pub fn new_lazy<F, T, W: LazySignalType<Inner = T>>(func: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        let val = AnyData::new(W::stale());
+        let func = Box::new(move |val: &BoxAnyData, observed: bool| {
+            #[cfg(not(feature = "unsafe-cell"))]
+            let is_stale = val.borrow().downcast_ref::<W>().unwrap().is_stale();
+            #[cfg(feature = "unsafe-cell")]
+            let is_stale = unsafe { &*val.get() }
+                .downcast_ref::<W>()
+                .unwrap()
+                .is_stale();
+
+            if !observed {
+                if !is_stale {
+                    #[cfg(not(feature = "unsafe-cell"))]
+                    let mut old_any = val.borrow_mut();
+                    #[cfg(feature = "unsafe-cell")]
+                    let old_any: &mut dyn Any = unsafe { &mut *val.get() };
+                    *old_any.downcast_mut::<W>().unwrap() = W::stale();
+                }
+                return false;
+            }
+            let new = func();
+
+            #[cfg(not(feature = "unsafe-cell"))]
+            let mut old_any = val.borrow_mut();
+            #[cfg(feature = "unsafe-cell")]
+            let old_any: &mut dyn Any = unsafe { &mut *val.get() };
+
+            let old = old_any.downcast_mut::<W>().unwrap();
+            let eq = !is_stale && old.is_eq(&new);
+            *old = W::new(new);
+            !eq
+        });
+        Self { func, value: val }
+    }
+
+    /// Runs the function and returns true if the value changed. Lazy functions
+    /// only compute their value when `observed`.
+    pub fn run(&self, observed: bool) -> bool {
+        (self.func)(&self.value.0, observed)
     }
 }
@@ -3,13 +3,14 @@ mod arr_vec;
 mod dyn_func;
 mod signal_set;
 mod u15_bool;
+#[cfg(feature = "large-ids")]
+mod u31_bool;
 
 pub(crate) use any_data::AnyData;
 pub(crate) use arr_vec::ArrVec;
 pub(crate) use dyn_func::DynFunc;
 pub(crate) use signal_set::SignalSet;
+#[cfg(not(feature = "large-ids"))]
 pub(crate) use u15_bool::u15Bool;
-
-// kept for future use if a feature "large-indexes" is added
-// mod u31_bool;
-// pub(crate) use u31_bool::u31Bool;
+#[cfg(feature = "large-ids")]
+pub(crate) use u31_bool::u31Bool;
@@ -66,30 +66,30 @@ impl<const N: usize, T: Ord + Eq + Copy> Default for SignalSet<N, T> {
 #[test]
 fn test_retain() {
     use crate::arena_tree::NodeId;
-    use crate::primitives::u15Bool;
     use crate::runtimes::ServerRuntime;
     use crate::signals::SignalId;
+    use crate::signals::SignalIndex;
 
     let sig1_scope1 = SignalId {
-        id: u15Bool::new(1, false),
+        id: SignalIndex::new(1, false),
         sx: NodeId::from(1),
         rt: ServerRuntime::from(4),
     };
 
     let sig2_scope1 = SignalId {
-        id: u15Bool::new(2, false),
+        id: SignalIndex::new(2, false),
         sx: NodeId::from(1),
         rt: ServerRuntime::from(4),
     };
 
     let sig1_scope2 = SignalId {
-        id: u15Bool::new(1, false),
+        id: SignalIndex::new(1, false),
         sx: NodeId::from(2),
         rt: ServerRuntime::from(4),
     };
 
     let sig2_scope2 = SignalId {
-        id: u15Bool::new(2, false),
+        id: SignalIndex::new(2, false),
         sx: NodeId::from(2),
         rt: ServerRuntime::from(4),
     };
@@ -1,8 +1,6 @@
-#![allow(dead_code)]
-
 use std::num::NonZeroU32;
 
-/// A u15 (unsigned integer) with one bit used for representing a boolean.
+/// A u31 (unsigned integer) with one bit used for representing a boolean.
 /// It's main purpose is to transparently add on a boolean to a number.
 /// The boolean is typically used for indicated if the value is "dirty"
 /// "initialized".
@@ -20,7 +18,6 @@ use std::num::NonZeroU32;
 /// assert_eq!(v1, v2);
 /// ```
 ///
-
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone)]
 pub(crate) struct u31Bool(NonZeroU32);
@@ -35,14 +32,13 @@ const NONE_LAST_BIT: u32 = 0b1111_1111_1111_1111_1111_1111_1111_1110;
 // - set the last bit according to the bool
 // - store in a NonZeroU32
 impl u31Bool {
-    /// The maximum value the u15 accepts:
+    /// The maximum value the u31 accepts:
     ///
     /// `2^31 - 2 = 2_147_483_646`
-
     pub const MAX: u32 = 2_147_483_646;
 
     /// ## Warning
-    /// Panics if the num (usize) is bigger than `u15Bool::MAX`
+    /// Panics if the num (usize) is bigger than `u31Bool::MAX`
     pub fn new(num: usize, val: bool) -> Self {
         Self(set_last_bit_bool(shift_one(num as u32 + 1), val))
     }
@@ -56,12 +52,12 @@ impl u31Bool {
     }
 
     #[inline]
-    pub fn usize(&self) -> usize {
-        self.u31() as usize
+    pub fn as_usize(&self) -> usize {
+        self.as_u31() as usize
     }
 
     #[inline]
-    pub fn u31(&self) -> u32 {
+    pub fn as_u31(&self) -> u32 {
         unshift_one(self.0) - 1
     }
 }
@@ -77,7 +73,7 @@ impl Eq for u31Bool {}
 impl PartialOrd for u31Bool {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        unshift_one(self.0).partial_cmp(&unshift_one(other.0))
+        Some(self.cmp(other))
     }
 }
 
@@ -117,39 +113,39 @@ fn get_bool(non_zero: NonZeroU32) -> bool {
 fn test_0_bool() {
     let val = u31Bool::new(0, false);
     assert_eq!(val.bool(), false);
-    assert_eq!(val.u31(), 0);
+    assert_eq!(val.as_u31(), 0);
 
     let val = u31Bool::new(0, true);
     assert_eq!(val.bool(), true);
-    assert_eq!(val.u31(), 0);
+    assert_eq!(val.as_u31(), 0);
 }
 
 #[test]
 fn test_1_bool() {
     let val = u31Bool::new(1, false);
     assert_eq!(val.bool(), false);
-    assert_eq!(val.u31(), 1);
+    assert_eq!(val.as_u31(), 1);
 
     let val = u31Bool::new(1, true);
     assert_eq!(val.bool(), true);
-    assert_eq!(val.u31(), 1);
+    assert_eq!(val.as_u31(), 1);
 }
 
 #[test]
 fn test_max_bool() {
     let mut val = u31Bool::new(u31Bool::MAX as usize, false);
     assert_eq!(val.bool(), false);
-    assert_eq!(val.u31(), u31Bool::MAX);
+    assert_eq!(val.as_u31(), u31Bool::MAX);
 
     val.set_bool(true);
     assert_eq!(val.bool(), true);
-    assert_eq!(val.u31(), u31Bool::MAX);
+    assert_eq!(val.as_u31(), u31Bool::MAX);
 
     let mut val = u31Bool::new(u31Bool::MAX as usize, true);
     assert_eq!(val.bool(), true);
-    assert_eq!(val.u31(), u31Bool::MAX);
+    assert_eq!(val.as_u31(), u31Bool::MAX);
 
     val.set_bool(false);
     assert_eq!(val.bool(), false);
-    assert_eq!(val.u31(), u31Bool::MAX);
+    assert_eq!(val.as_u31(), u31Bool::MAX);
 }
@@ -0,0 +1,160 @@
+use std::rc::Rc;
+
+use crate::{
+    primitives::AnyData,
+    runtimes::Runtime,
+    signals::{spawner::spawner, Data, EqData},
+    Scope, Signal,
+};
+
+/// The state of an async fetch, with a signal for whether it is loading, one for the
+/// last value fetched and one for the error of the last fetch, if it failed.
+///
+/// The fetch is started when the resource is created and started again when the signals
+/// it reads change or when [refetch()](Self::refetch()) is called, which cancels the
+/// fetch that is in flight, if any. While a fetch is in flight, and if it fails, the
+/// value of the last successful one is kept.
+///
+/// Like a [Signal] it is created in a [Scope] and implements [Copy].
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal, Resource};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let user_id = signal!(sc, 1);
+///
+/// let user = Resource::new(sc, async move || fetch_user(user_id.get()).await);
+/// assert_eq!(user.value().cloned(), Some("user 1".to_string()));
+///
+/// user_id.set(0);
+/// assert_eq!(user.error().cloned(), Some("not found"));
+/// assert_eq!(user.value().cloned(), Some("user 1".to_string()));
+/// assert!(!user.loading().get());
+///
+/// # async fn fetch_user(id: u32) -> Result<String, &'static str> {
+/// #     if id == 0 {
+/// #         Err("not found")
+/// #     } else {
+/// #         Ok(format!("user {id}"))
+/// #     }
+/// # }
+/// ```
+pub struct Resource<T: 'static, E: 'static, RT: Runtime + 'static> {
+    loading: Signal<EqData<bool>, RT>,
+    value: Signal<Data<Option<T>>, RT>,
+    error: Signal<Data<Option<E>>, RT>,
+    /// Read by the fetch so that setting it fetches again
+    trigger: Signal<Data<()>, RT>,
+}
+
+impl<T, E, RT: Runtime> Clone for Resource<T, E, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, RT: Runtime> Copy for Resource<T, E, RT> {}
+
+impl<T, E, RT> Resource<T, E, RT>
+where
+    T: 'static,
+    E: 'static,
+    RT: Runtime + Send + Sync + 'static,
+{
+    pub fn new<F>(sx: Scope<RT>, fetch: F) -> Self
+    where
+        F: AsyncFn() -> Result<T, E> + 'static,
+    {
+        let resource = Self {
+            loading: Signal::data(sx, AnyData::new(EqData(true))),
+            value: Signal::data(sx, AnyData::new(Data(None::<T>))),
+            error: Signal::data(sx, AnyData::new(Data(None::<E>))),
+            trigger: Signal::data(sx, AnyData::new(Data(()))),
+        };
+        let fetch = Rc::new(fetch);
+        spawner(sx, move || {
+            let fetch = fetch.clone();
+            async move { resource.fetch(&*fetch).await }
+        });
+        resource
+    }
+
+    async fn fetch<F: AsyncFn() -> Result<T, E>>(self, fetch: &F) {
+        self.trigger.with(|_| ());
+        self.loading.set(true);
+        let result = fetch().await;
+
+        let sx = self.loading.scope();
+        sx.batch(|| {
+            match result {
+                Ok(value) => {
+                    self.value.set(Some(value));
+                    if self.error.with_untracked(Option::is_some) {
+                        self.error.set(None);
+                    }
+                }
+                Err(error) => self.error.set(Some(error)),
+            }
+            self.loading.set(false);
+        })
+    }
+
+    /// Whether a fetch is in flight.
+    pub fn loading(&self) -> Signal<EqData<bool>, RT> {
+        self.loading
+    }
+
+    /// The value of the last successful fetch.
+    pub fn value(&self) -> Signal<Data<Option<T>>, RT> {
+        self.value
+    }
+
+    /// The error of the last fetch, if it failed.
+    pub fn error(&self) -> Signal<Data<Option<E>>, RT> {
+        self.error
+    }
+
+    /// Cancels the fetch in flight, if any, and fetches again.
+    pub fn refetch(&self) {
+        self.trigger.set(())
+    }
+}
+
+#[test]
+fn test_resource() {
+    use crate::{runtimes::TestClientRuntime, signal, tests::Deferred};
+
+    let sc = TestClientRuntime::new_root_scope();
+    let id_sig = signal!(sc, 1);
+    let response = Rc::new(Deferred::<Result<&str, &str>>::new());
+
+    let fetched = response.clone();
+    let resource = Resource::new(sc, async move || {
+        let id = id_sig.get();
+        fetched.wait().await.map(|name| format!("{id}: {name}"))
+    });
+    let state = move || {
+        (
+            resource.loading().get(),
+            resource.value().cloned(),
+            resource.error().get(),
+        )
+    };
+    assert_eq!(state(), (true, None, None));
+
+    response.send(Ok("a"));
+    assert_eq!(state(), (false, Some("1: a".to_string()), None));
+
+    // the last value is kept while fetching and when it fails
+    id_sig.set(2);
+    assert_eq!(state(), (true, Some("1: a".to_string()), None));
+    response.send(Err("failed"));
+    assert_eq!(state(), (false, Some("1: a".to_string()), Some("failed")));
+
+    // refetching cancels the fetch in flight
+    resource.refetch();
+    id_sig.set(3);
+    resource.refetch();
+    response.send(Ok("b"));
+    assert_eq!(state(), (false, Some("3: b".to_string()), None));
+}
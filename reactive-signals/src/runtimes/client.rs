@@ -1,6 +1,6 @@
-use crate::CellType;
+use crate::{CellType, ReactiveError};
 
-use super::{Runtime, RuntimeInner, Scope};
+use super::{timers, Runtime, RuntimeInner, Scope};
 
 thread_local! {
   pub static RUNTIME: SingleClientRuntime = Default::default();
@@ -52,9 +52,36 @@ impl Runtime for ClientRuntime {
         RUNTIME.with(|rt| f(&rt.rt_ref()))
     }
 
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, ReactiveError>
+    where
+        F: FnOnce(&RuntimeInner<ClientRuntime>) -> Result<T, ReactiveError>,
+    {
+        RUNTIME.with(|rt| rt.try_rt_ref().and_then(|rt| f(&rt)))
+    }
+
 }
 
 impl  ClientRuntime {
+    /// Runs the timer signals that are due at the time and returns the time until the
+    /// next one is, if any. It is meant to be called from the event loop of the application.
+    ///
+    /// The time is counted from any fixed origin, such as `performance.now()` in a browser,
+    /// and a time earlier than the one of a previous call is ignored. The interval of a
+    /// timer signal starts at the time of the last call.
+    ///
+    /// ```no_run
+    /// use reactive_signals::runtimes::ClientRuntime;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// while let Some(next) = ClientRuntime::run_timers(start.elapsed()) {
+    ///     std::thread::sleep(next);
+    /// }
+    /// ```
+    pub fn run_timers(now: std::time::Duration) -> Option<std::time::Duration> {
+        timers::wake_due(ClientRuntime, now)
+    }
+
     pub fn new_root_scope() -> Scope<ClientRuntime> {
         RUNTIME.with(|rt| {
             #[allow(unused_mut)]
@@ -62,13 +89,19 @@ impl  ClientRuntime {
             if data.in_use() {
                 panic!("Runtime is already used. Make sure to not call new_root_scope() more than once on a thread");
             }
-            let mut rti = RuntimeInner::new();
-            let sx = rti.scope_tree.init(Default::default());
-            *data = rti;
+            if data.scope_tree.is_retired() {
+                panic!("Runtime has been discarded too many times. The root scope of a thread can be created at most 65535 times");
+            }
+            // the tree is reused to keep its generation
+            let sx = data.scope_tree.init(Default::default());
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(scope = ?sx, "root scope created");
 
             Scope {
                 sx,
                 rt: ClientRuntime,
+                generation: data.scope_tree.generation(sx),
             }
     
         })
@@ -95,6 +128,11 @@ impl SingleClientRuntime {
         self.0.borrow_mut()
     }
 
+    #[inline]
+    fn try_rt_ref(&self) -> Result<std::cell::Ref<'_, RuntimeInner<ClientRuntime>>, ReactiveError> {
+        self.0.try_borrow().map_err(|_| ReactiveError::ReentrantBorrow)
+    }
+
 }
 #[cfg(feature = "unsafe-cell")]
 impl SingleClientRuntime {
@@ -107,4 +145,9 @@ impl SingleClientRuntime {
     fn rt_mut(&self) -> &mut RuntimeInner<ClientRuntime> {
         unsafe { &mut *self.0.get() }
     }
+
+    #[inline]
+    fn try_rt_ref(&self) -> Result<&RuntimeInner<ClientRuntime>, ReactiveError> {
+        Ok(self.rt_ref())
+    }
 }
\ No newline at end of file
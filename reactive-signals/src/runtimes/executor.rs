@@ -0,0 +1,210 @@
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Wake, Waker},
+    thread::{self, ThreadId},
+};
+
+use super::Runtime;
+
+pub(crate) type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The index of a task in the executor together with a number that
+/// is unique for the runtime, so that a reused index isn't mistaken
+/// for the task that used it before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TaskId {
+    index: usize,
+    unique: u64,
+}
+
+enum Slot {
+    Free,
+    /// A task waiting to be woken
+    Idle(u64, Task),
+    /// A task that has been taken out of its slot to be polled
+    Polling(u64),
+}
+
+impl Slot {
+    fn is(&self, id: TaskId) -> bool {
+        matches!(self, Slot::Idle(unique, _) | Slot::Polling(unique) if *unique == id.unique)
+    }
+}
+
+/// A single-threaded executor for the futures of the async signals of a runtime.
+///
+/// The tasks are polled on the thread of the runtime as soon as they are woken. A task
+/// that is woken on another thread is polled by [run_pending()](super::Runtime::run_pending())
+/// or together with the next one woken on the runtime's thread.
+#[derive(Default)]
+pub(crate) struct Executor {
+    slots: Cell<Vec<Slot>>,
+    next_unique: Cell<u64>,
+    /// The tasks to poll, shared with the wakers
+    woken: Arc<Mutex<VecDeque<TaskId>>>,
+    /// Whether the tasks are being polled
+    polling: Cell<bool>,
+}
+
+impl Executor {
+    fn insert(&self, task: Task) -> TaskId {
+        let unique = self.next_unique.get();
+        self.next_unique.set(unique + 1);
+
+        let mut slots = self.slots.take();
+        let index = match slots.iter().position(|s| matches!(s, Slot::Free)) {
+            Some(index) => index,
+            None => {
+                slots.push(Slot::Free);
+                slots.len() - 1
+            }
+        };
+        slots[index] = Slot::Idle(unique, task);
+        self.slots.set(slots);
+
+        let id = TaskId { index, unique };
+        self.woken.lock().unwrap().push_back(id);
+        id
+    }
+
+    /// Removes the task and returns it, in order for it to be dropped
+    /// outside of the runtime. A task that is being polled is dropped
+    /// when its poll returns.
+    fn remove(&self, id: TaskId) -> Option<Task> {
+        let mut slots = self.slots.take();
+        let task = match slots.get_mut(id.index) {
+            Some(slot) if slot.is(id) => match mem::replace(slot, Slot::Free) {
+                Slot::Idle(_, task) => Some(task),
+                _ => None,
+            },
+            _ => None,
+        };
+        self.slots.set(slots);
+        task
+    }
+
+    /// Removes all the tasks and returns them, in order for them to
+    /// be dropped outside of the runtime.
+    pub(crate) fn take_tasks(&self) -> Vec<Task> {
+        let slots = self.slots.take().into_iter();
+        slots
+            .filter_map(|slot| match slot {
+                Slot::Idle(_, task) => Some(task),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Takes the next woken task out of its slot, skipping the
+    /// tasks that have been removed.
+    fn next_woken(&self) -> Option<(TaskId, Task)> {
+        loop {
+            let id = self.woken.lock().unwrap().pop_front()?;
+            let mut slots = self.slots.take();
+            let task = match slots.get_mut(id.index) {
+                Some(slot @ Slot::Idle(..)) if slot.is(id) => {
+                    match mem::replace(slot, Slot::Polling(id.unique)) {
+                        Slot::Idle(_, task) => Some(task),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            self.slots.set(slots);
+            if let Some(task) = task {
+                return Some((id, task));
+            }
+        }
+    }
+
+    /// Puts a polled task back in its slot. Returns it, for it to be dropped,
+    /// if it is done or if it was removed while it was polled.
+    fn put_back(&self, id: TaskId, task: Task, done: bool) -> Option<Task> {
+        let mut slots = self.slots.take();
+        let task = match slots.get_mut(id.index) {
+            Some(slot @ Slot::Polling(_)) if slot.is(id) => {
+                if done {
+                    *slot = Slot::Free;
+                    Some(task)
+                } else {
+                    *slot = Slot::Idle(id.unique, task);
+                    None
+                }
+            }
+            _ => Some(task),
+        };
+        self.slots.set(slots);
+        task
+    }
+}
+
+/// Adds the future to the executor of the runtime and polls it.
+pub(crate) fn spawn<RT: Runtime + Send + Sync + 'static>(rt: RT, task: Task) -> TaskId {
+    let id = rt.with_ref(|rt| rt.executor.insert(task));
+    run_tasks(rt);
+    id
+}
+
+/// Drops the future, if it hasn't completed.
+pub(crate) fn cancel<RT: Runtime>(rt: RT, id: TaskId) {
+    let task = rt.with_ref(|rt| rt.executor.remove(id));
+    drop(task);
+}
+
+/// Polls the woken tasks until there are none left. When called while
+/// the tasks are being polled it does nothing, as the ongoing call
+/// polls them.
+pub(crate) fn run_tasks<RT: Runtime + Send + Sync + 'static>(rt: RT) {
+    if rt.with_ref(|rt| rt.executor.polling.replace(true)) {
+        return;
+    }
+    let _guard = PollingGuard(rt);
+    while let Some((id, mut task)) = rt.with_ref(|rt| rt.executor.next_woken()) {
+        let waker = Waker::from(Arc::new(TaskWaker {
+            rt,
+            id,
+            thread: thread::current().id(),
+            woken: rt.with_ref(|rt| rt.executor.woken.clone()),
+        }));
+        let done = task
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker))
+            .is_ready();
+        let task = rt.with_ref(|rt| rt.executor.put_back(id, task, done));
+        drop(task);
+    }
+}
+
+/// Ends the polling when the tasks have been polled or one of them panics
+struct PollingGuard<RT: Runtime>(RT);
+
+impl<RT: Runtime> Drop for PollingGuard<RT> {
+    fn drop(&mut self) {
+        self.0.with_ref(|rt| rt.executor.polling.set(false));
+    }
+}
+
+struct TaskWaker<RT> {
+    rt: RT,
+    id: TaskId,
+    thread: ThreadId,
+    woken: Arc<Mutex<VecDeque<TaskId>>>,
+}
+
+impl<RT: Runtime + Send + Sync + 'static> Wake for TaskWaker<RT> {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.lock().unwrap().push_back(self.id);
+        if thread::current().id() == self.thread {
+            run_tasks(self.rt);
+        }
+    }
+}
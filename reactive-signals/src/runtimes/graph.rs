@@ -0,0 +1,185 @@
+use std::fmt::Write;
+
+use crate::signals::SignalId;
+
+use super::{Runtime, ScopeSnapshot, SignalKind, Snapshot};
+
+/// Exports a [Snapshot] as a graph, with the scopes as nested clusters, the signals as
+/// nodes labeled with their index and kind, and an edge from each signal to each of its
+/// listeners. The triggers of the snapshot and the signals they affected are highlighted.
+///
+/// The output only depends on the structure so that it can be compared between runs.
+///
+/// ```rust
+/// use reactive_signals::{runtimes::{ClientRuntime, Runtime}, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let count = signal!(sc, 1);
+/// let _double = signal!(sc, move || count.get() * 2);
+///
+/// let mermaid = sc.runtime().snapshot().to_mermaid();
+/// assert!(mermaid.contains(r#"s0_0["0: data eq"]"#));
+/// assert!(mermaid.contains("s0_0 --> s0_1"));
+/// ```
+impl<RT: Runtime> Snapshot<RT> {
+    /// The graph in the [DOT](https://graphviz.org/doc/info/lang.html) language of Graphviz.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n    node [shape=box];\n");
+        let affected = self.affected();
+        if let Some(root) = self.scopes.first() {
+            self.write_dot_scope(&mut out, root, &affected, 1);
+        }
+        for (id, listener) in self.edges() {
+            writeln!(out, "    {} -> {};", node(id), node(listener)).unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn write_dot_scope(
+        &self,
+        out: &mut String,
+        scope: &ScopeSnapshot<RT>,
+        affected: &[SignalId<RT>],
+        depth: usize,
+    ) {
+        let indent = "    ".repeat(depth);
+        writeln!(out, "{indent}subgraph cluster_{} {{", scope.index).unwrap();
+        writeln!(out, "{indent}    label=\"scope {}\";", scope.index).unwrap();
+        for signal in &scope.signals {
+            let style = if self.triggers.contains(&signal.id) {
+                ", style=filled, fillcolor=orange"
+            } else if affected.contains(&signal.id) {
+                ", style=filled, fillcolor=yellow"
+            } else {
+                ""
+            };
+            let (id, label) = (node(signal.id), label(signal.id, signal.kind));
+            writeln!(out, "{indent}    {id} [label=\"{label}\"{style}];").unwrap();
+        }
+        for child in self.children(scope) {
+            self.write_dot_scope(out, child, affected, depth + 1);
+        }
+        writeln!(out, "{indent}}}").unwrap();
+    }
+
+    /// The graph as a [Mermaid](https://mermaid.js.org/syntax/flowchart.html) flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+        if let Some(root) = self.scopes.first() {
+            self.write_mermaid_scope(&mut out, root, 1);
+        }
+        for (id, listener) in self.edges() {
+            writeln!(out, "    {} --> {}", node(id), node(listener)).unwrap();
+        }
+        let affected = self.affected();
+        if !affected.is_empty() {
+            out.push_str("    classDef trigger fill:orange\n    classDef affected fill:yellow\n");
+        }
+        for id in affected {
+            let class = if self.triggers.contains(&id) {
+                "trigger"
+            } else {
+                "affected"
+            };
+            writeln!(out, "    class {} {class}", node(id)).unwrap();
+        }
+        out
+    }
+
+    fn write_mermaid_scope(&self, out: &mut String, scope: &ScopeSnapshot<RT>, depth: usize) {
+        let indent = "    ".repeat(depth);
+        writeln!(out, "{indent}subgraph scope{0} [scope {0}]", scope.index).unwrap();
+        for signal in &scope.signals {
+            let (id, label) = (node(signal.id), label(signal.id, signal.kind));
+            writeln!(out, "{indent}    {id}[\"{label}\"]").unwrap();
+        }
+        for child in self.children(scope) {
+            self.write_mermaid_scope(out, child, depth + 1);
+        }
+        writeln!(out, "{indent}end").unwrap();
+    }
+
+    fn children<'a>(
+        &'a self,
+        scope: &'a ScopeSnapshot<RT>,
+    ) -> impl Iterator<Item = &'a ScopeSnapshot<RT>> {
+        (self.scopes.iter()).filter(move |child| child.parent == Some(scope.index))
+    }
+}
+
+fn node<RT: Runtime>(id: SignalId<RT>) -> String {
+    format!("s{}_{}", id.sx.index(), id.index())
+}
+
+/// The index of the signal in its scope followed by its kind, i.e. `data` or `func`,
+/// then `lazy` and `eq` when they apply and `server` or `client` when it only runs on one side.
+fn label<RT: Runtime>(id: SignalId<RT>, kind: SignalKind) -> String {
+    let mut label = format!(
+        "{}: {}",
+        id.index(),
+        if kind.func { "func" } else { "data" }
+    );
+    let flags = [
+        (kind.lazy, " lazy"),
+        (kind.eq, " eq"),
+        (!kind.client, " server"),
+        (!kind.server, " client"),
+    ];
+    for (_, flag) in flags.iter().filter(|(set, _)| *set) {
+        label.push_str(flag);
+    }
+    label
+}
+
+#[test]
+fn test_graph() {
+    use crate::{runtimes::TestClientRuntime, signal};
+
+    let sc = TestClientRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let _lazy_sig = signal!(sc, lazy, move || num_sig.get() + 1);
+    let child = sc.new_child();
+    let _server_sig = signal!(child, server, move || num_sig.get() * 2);
+
+    let rt = sc.runtime();
+    rt.record_snapshots();
+    num_sig.set(2);
+    let snapshot = &rt.take_snapshots()[1];
+
+    assert_eq!(
+        snapshot.to_dot(),
+        r#"digraph {
+    node [shape=box];
+    subgraph cluster_0 {
+        label="scope 0";
+        s0_0 [label="0: data eq", style=filled, fillcolor=orange];
+        s0_1 [label="1: func lazy eq"];
+        subgraph cluster_1 {
+            label="scope 1";
+            s1_0 [label="0: func eq server", style=filled, fillcolor=yellow];
+        }
+    }
+    s0_0 -> s1_0;
+}
+"#
+    );
+
+    assert_eq!(
+        snapshot.to_mermaid(),
+        r#"flowchart TD
+    subgraph scope0 [scope 0]
+        s0_0["0: data eq"]
+        s0_1["1: func lazy eq"]
+        subgraph scope1 [scope 1]
+            s1_0["0: func eq server"]
+        end
+    end
+    s0_0 --> s1_0
+    classDef trigger fill:orange
+    classDef affected fill:yellow
+    class s0_0 trigger
+    class s1_0 affected
+"#
+    );
+}
@@ -1,18 +1,51 @@
-use std::{
-    cell::Cell,
-    ops::{Index, IndexMut},
-};
+use std::cell::Cell;
 
-use crate::arena_tree::Tree;
+use crate::arena_tree::{FlagVec, NodeId, Tree};
+use crate::iter::{IdVecIter, NodeResolver};
 
-use crate::{signals::SignalId, ScopeInner};
+use crate::{
+    error::{CycleError, ReactiveError},
+    signals::{propagate_change, SignalId},
+    ScopeInner,
+};
 
-use super::Runtime;
+#[cfg(feature = "snapshots")]
+use super::snapshot::{Snapshot, ValueTypes};
+use super::{
+    executor::{Executor, Task},
+    timers::Timers,
+    Runtime,
+};
 
 #[derive(Default)]
 pub struct RuntimeInner<RT: Runtime> {
     pub(crate) scope_tree: Tree<ScopeInner<RT>>,
     running_signal: Cell<Option<SignalId<RT>>>,
+    /// The number of nested batches currently running
+    batch_depth: Cell<u16>,
+    /// The signals that changed during the current batch
+    batched_changes: Cell<Vec<SignalId<RT>>>,
+    /// The signals that are currently running or propagating a change
+    propagation_path: Cell<Vec<SignalId<RT>>>,
+    /// The dependents of the changed signals that the current propagation
+    /// hasn't brought up to date yet, sorted. The dirty ones have a changed source.
+    pending: Cell<Vec<SignalId<RT>>>,
+    /// The first cycle detected during the current propagation
+    cycle_error: Cell<Option<CycleError>>,
+    /// The signals registered for polling, sorted
+    polled: Cell<Vec<SignalId<RT>>>,
+    /// The polled signals that changed since they were last drained
+    polled_changes: Cell<Vec<SignalId<RT>>>,
+    /// Polls the futures of the async signals
+    pub(crate) executor: Executor,
+    /// The clock of the timer signals
+    pub(crate) timers: Timers,
+    /// The kinds of the signal values, for the snapshots
+    #[cfg(feature = "snapshots")]
+    pub(crate) value_types: ValueTypes,
+    /// The recorded snapshots, if recording
+    #[cfg(feature = "snapshots")]
+    pub(super) snapshots: Cell<Option<Vec<Snapshot<RT>>>>,
 }
 
 impl<RT: Runtime> RuntimeInner<RT> {
@@ -20,6 +53,19 @@ impl<RT: Runtime> RuntimeInner<RT> {
         Self {
             scope_tree: Tree::create(),
             running_signal: Cell::new(None),
+            batch_depth: Cell::new(0),
+            batched_changes: Cell::new(Vec::new()),
+            propagation_path: Cell::new(Vec::new()),
+            pending: Cell::new(Vec::new()),
+            cycle_error: Cell::new(None),
+            polled: Cell::new(Vec::new()),
+            polled_changes: Cell::new(Vec::new()),
+            executor: Default::default(),
+            timers: Default::default(),
+            #[cfg(feature = "snapshots")]
+            value_types: Default::default(),
+            #[cfg(feature = "snapshots")]
+            snapshots: Cell::new(None),
         }
     }
 
@@ -27,13 +73,60 @@ impl<RT: Runtime> RuntimeInner<RT> {
         self.scope_tree.is_initialized()
     }
 
-    pub fn discard(&mut self) {
+    /// Resets the runtime and returns the futures of the async signals that were
+    /// pending, to be dropped outside of the runtime borrow as they may use signals.
+    pub fn discard(&mut self) -> Vec<Task> {
+        let tasks = self.executor.take_tasks();
         if self.in_use() {
             // also sets the tree to not initialized
             self.scope_tree.discard_all();
+            // only the tree is kept, for the generations of its slots
+            let scope_tree = std::mem::take(&mut self.scope_tree);
+            *self = Self {
+                scope_tree,
+                ..Self::new()
+            };
+        }
+        tasks
+    }
+
+    /// Returns an error if the scope with the given generation, or the
+    /// runtime, has been discarded.
+    #[inline]
+    pub(crate) fn try_check_scope(&self, sx: NodeId, generation: u16) -> Result<(), ReactiveError> {
+        if !self.in_use() {
+            Err(ReactiveError::DiscardedRuntime)
+        } else if !self.scope_tree.is_alive(sx, generation) {
+            Err(ReactiveError::DiscardedScope)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Panics with a description of the problem when the scope with the
+    /// given generation, or the runtime, has been discarded.
+    #[inline]
+    pub(crate) fn check_scope(&self, sx: NodeId, generation: u16) {
+        if let Err(error) = self.try_check_scope(sx, generation) {
+            panic!("cannot use the scope {sx:?}: {error}");
         }
     }
 
+    /// Panics with a description of the problem when the signal's
+    /// scope, or the runtime, has been discarded.
+    #[inline]
+    pub(crate) fn check_signal(&self, id: SignalId<RT>, generation: u16) {
+        if let Err(error) = self.try_check_scope(id.sx, generation) {
+            panic!("cannot use the signal {id:?}: {error}");
+        }
+    }
+
+    /// The scope that the signal belongs to
+    #[inline]
+    pub(crate) fn scope_of(&self, id: SignalId<RT>) -> &ScopeInner<RT> {
+        &self.scope_tree[id.sx]
+    }
+
     pub(crate) fn get_running_signal(&self) -> Option<SignalId<RT>> {
         self.running_signal.get()
     }
@@ -43,18 +136,185 @@ impl<RT: Runtime> RuntimeInner<RT> {
         self.running_signal.set(signal);
         previous
     }
+
+    pub(crate) fn start_batch(&self) {
+        self.batch_depth.set(self.batch_depth.get() + 1);
+    }
+
+    /// Ends a batch and returns the changed signals when it was the outermost one.
+    pub(crate) fn end_batch(&self) -> Option<Vec<SignalId<RT>>> {
+        let depth = self.batch_depth.get() - 1;
+        self.batch_depth.set(depth);
+        (depth == 0).then(|| self.batched_changes.take())
+    }
+
+    /// Forgets the batched changes of the discarded scopes
+    pub(crate) fn remove_batched_scopes(&self, discarded_scopes: &FlagVec) {
+        let mut changes = self.batched_changes.take();
+        changes.retain(|s| !discarded_scopes.get(s.sx.index()));
+        self.batched_changes.set(changes);
+    }
+
+    /// Propagates the change directly or, if a batch is running,
+    /// when the batch ends.
+    pub(crate) fn signal_changed(&self, sig: SignalId<RT>) {
+        self.record_change(sig);
+        if self.batch_depth.get() == 0 {
+            if self.enter_signal(sig) {
+                propagate_change(self, sig);
+                self.leave_signal();
+            }
+            #[cfg(feature = "snapshots")]
+            self.snapshot_changes(sig.rt, &[sig]);
+        } else {
+            let mut changes = self.batched_changes.take();
+            changes.push(sig);
+            self.batched_changes.set(changes);
+        }
+    }
 }
 
-impl<RT: Runtime> Index<SignalId<RT>> for RuntimeInner<RT> {
-    type Output = ScopeInner<RT>;
+// polled signals
+impl<RT: Runtime> RuntimeInner<RT> {
+    pub(crate) fn register_polled(&self, mut sig: SignalId<RT>) {
+        sig.set_dirty(false);
+        let mut polled = self.polled.take();
+        if let Err(pos) = polled.binary_search(&sig) {
+            polled.insert(pos, sig);
+        }
+        self.polled.set(polled);
+    }
 
-    fn index(&self, index: SignalId<RT>) -> &Self::Output {
-        &self.scope_tree[index.sx]
+    /// Records the change of the signal if it is registered for polling
+    pub(crate) fn record_change(&self, mut sig: SignalId<RT>) {
+        let polled = self.polled.take();
+        if polled.binary_search(&sig).is_ok() {
+            sig.set_dirty(false);
+            let mut changes = self.polled_changes.take();
+            changes.push(sig);
+            self.polled_changes.set(changes);
+        }
+        self.polled.set(polled);
+    }
+
+    /// The polled signals that changed since the previous call, sorted and without duplicates
+    pub(crate) fn drain_changed(&self) -> Vec<SignalId<RT>> {
+        let mut changes = self.polled_changes.take();
+        changes.sort();
+        changes.dedup();
+        changes
+    }
+
+    /// Forgets the polled signals of the discarded scopes
+    pub(crate) fn remove_polled_scopes(&self, discarded_scopes: &FlagVec) {
+        let is_kept = |s: &SignalId<RT>| !discarded_scopes.get(s.sx.index());
+        let mut polled = self.polled.take();
+        polled.retain(is_kept);
+        self.polled.set(polled);
+        let mut changes = self.polled_changes.take();
+        changes.retain(is_kept);
+        self.polled_changes.set(changes);
+    }
+}
+
+// pending signals of a propagation
+impl<RT: Runtime> RuntimeInner<RT> {
+    /// Sets the pending signals of a propagation and returns the ones of the
+    /// propagation it is nested in, which are restored when it ends.
+    pub(crate) fn replace_pending(&self, pending: Vec<SignalId<RT>>) -> Vec<SignalId<RT>> {
+        self.pending.replace(pending)
+    }
+
+    pub(crate) fn is_pending(&self, id: SignalId<RT>) -> bool {
+        let pending = self.pending.take();
+        let found = pending.binary_search(&id).is_ok();
+        self.pending.set(pending);
+        found
+    }
+
+    /// Removes the signal from the pending ones and returns whether it is dirty
+    pub(crate) fn take_pending(&self, id: SignalId<RT>) -> bool {
+        let mut pending = self.pending.take();
+        let dirty = match pending.binary_search(&id) {
+            Ok(pos) => pending.remove(pos).is_dirty(),
+            Err(_) => false,
+        };
+        self.pending.set(pending);
+        dirty
+    }
+
+    /// Marks the pending listeners of the signal as dirty
+    pub(crate) fn mark_listeners_dirty(&self, id: SignalId<RT>) {
+        let mut pending = self.pending.take();
+        IdVecIter::new(self.scope_tree.node(id)).for_each(|listener| {
+            if let Ok(pos) = pending.binary_search(&listener) {
+                pending[pos].set_dirty(true);
+            }
+        });
+        self.pending.set(pending);
     }
 }
 
-impl<RT: Runtime> IndexMut<SignalId<RT>> for RuntimeInner<RT> {
-    fn index_mut(&mut self, index: SignalId<RT>) -> &mut Self::Output {
-        &mut self.scope_tree[index.sx]
+// cycle detection
+impl<RT: Runtime> RuntimeInner<RT> {
+    /// Adds the signal to the propagation path before running it or propagating
+    /// its change. Returns false, and doesn't add it, if the signal is already
+    /// in the path which means that there's a cycle.
+    pub(crate) fn enter_signal(&self, id: SignalId<RT>) -> bool {
+        let mut path = self.propagation_path.take();
+        let entered = if let Some(pos) = path.iter().position(|s| *s == id) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(id);
+            self.set_cycle_error(CycleError::new(&cycle));
+            false
+        } else {
+            path.push(id);
+            true
+        };
+        self.propagation_path.set(path);
+        entered
+    }
+
+    /// Whether a signal is running or propagating its change
+    #[cfg(feature = "snapshots")]
+    pub(crate) fn is_propagating(&self) -> bool {
+        let path = self.propagation_path.take();
+        let propagating = !path.is_empty();
+        self.propagation_path.set(path);
+        propagating
+    }
+
+    pub(crate) fn leave_signal(&self) {
+        let mut path = self.propagation_path.take();
+        path.pop();
+        self.propagation_path.set(path);
+    }
+
+    /// Keeps the first error only, as any subsequent ones are likely to be caused by it.
+    pub(crate) fn set_cycle_error(&self, error: CycleError) {
+        let current = self.cycle_error.take();
+        self.cycle_error.set(current.or(Some(error)));
+    }
+
+    /// Returns the cycle error, if any, when called after the outermost propagation.
+    pub(crate) fn take_cycle_error(&self) -> Result<(), CycleError> {
+        let path = self.propagation_path.take();
+        let is_outermost = path.is_empty();
+        self.propagation_path.set(path);
+
+        match is_outermost.then(|| self.cycle_error.take()).flatten() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Panics in debug builds if a cycle was detected. In release builds
+    /// the error is ignored.
+    pub(crate) fn check_cycle(&self) {
+        if let Err(error) = self.take_cycle_error() {
+            if cfg!(debug_assertions) {
+                panic!("{error}");
+            }
+        }
     }
 }
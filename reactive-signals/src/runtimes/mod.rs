@@ -16,15 +16,23 @@
 //! which is the index in the pool. As a consequence a pool can have at most 65k runtimes.
 //!
 mod client;
+pub(crate) mod executor;
+#[cfg(feature = "snapshots")]
+mod graph;
 mod inner;
 mod server;
+#[cfg(feature = "snapshots")]
+mod snapshot;
 mod test_client;
+pub(crate) mod timers;
 // mod staticrt;
 
-use crate::Scope;
+use crate::{signals::SignalId, ReactiveError, Scope};
 pub use client::ClientRuntime;
 pub(crate) use inner::RuntimeInner;
 pub use server::ServerRuntime;
+#[cfg(feature = "snapshots")]
+pub use snapshot::{ScopeSnapshot, SignalKind, SignalSnapshot, Snapshot, SnapshotDiff};
 pub use test_client::TestClientRuntime;
 // pub use staticrt::{StaticRuntime, StaticRuntimeId};
 
@@ -40,7 +48,53 @@ pub trait Runtime: Default + Copy {
     where
         F: FnOnce(&mut RuntimeInner<Self>) -> T;
 
+    /// Same as `with_ref` but returns an error instead of panicking when the
+    /// runtime is already mutably borrowed or doesn't exist.
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, ReactiveError>
+    where
+        F: FnOnce(&RuntimeInner<Self>) -> Result<T, ReactiveError>;
+
     fn discard(&self) {
-        self.with_mut(|rt| rt.discard());
+        let tasks = self.with_mut(|rt| rt.discard());
+        drop(tasks);
+    }
+
+    /// Returns the signals registered with [register_polled()](crate::Signal::register_polled())
+    /// that changed since the previous call, sorted and without duplicates.
+    fn drain_changed(&self) -> Vec<SignalId<Self>> {
+        self.with_ref(|rt| rt.drain_changed())
+    }
+
+    /// Polls the futures of the async signals that were woken on another thread.
+    ///
+    /// A future woken on the thread of the runtime is polled right away, whereas one
+    /// woken on another thread is queued until this is called, or until a future is
+    /// woken on the runtime's thread. It is meant to be called from the event loop
+    /// of the application.
+    fn run_pending(&self)
+    where
+        Self: Send + Sync + 'static,
+    {
+        executor::run_tasks(*self)
+    }
+
+    /// Captures the scopes and signals of the runtime, see [Snapshot].
+    #[cfg(feature = "snapshots")]
+    fn snapshot(&self) -> Snapshot<Self> {
+        self.with_ref(|rt| rt.snapshot(*self))
+    }
+
+    /// Starts recording a [Snapshot] after each change made outside of the signals,
+    /// i.e. after each `set` or `update` and after each batch, beginning with a
+    /// snapshot of the current state.
+    #[cfg(feature = "snapshots")]
+    fn record_snapshots(&self) {
+        self.with_ref(|rt| rt.record_snapshots(*self))
+    }
+
+    /// Stops recording and returns the recorded snapshots.
+    #[cfg(feature = "snapshots")]
+    fn take_snapshots(&self) -> Vec<Snapshot<Self>> {
+        self.with_ref(|rt| rt.take_snapshots())
     }
 }
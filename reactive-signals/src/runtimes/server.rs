@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 
 use super::{Runtime, RuntimeInner, Scope};
+use crate::ReactiveError;
 
 thread_local! {
   pub static RUNTIME_POOL: ServerRuntimePool = Default::default();
@@ -65,6 +66,22 @@ impl Runtime for ServerRuntime {
             f(rt)
         })
     }
+
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, ReactiveError>
+    where
+        F: FnOnce(&RuntimeInner<ServerRuntime>) -> Result<T, ReactiveError>,
+    {
+        RUNTIME_POOL.with(|pool| {
+            let pool = pool
+                .0
+                .try_borrow()
+                .map_err(|_| ReactiveError::ReentrantBorrow)?;
+            let rt = pool
+                .get(self.0 as usize)
+                .ok_or(ReactiveError::WrongRuntime)?;
+            f(rt)
+        })
+    }
 }
 
 #[derive(Default)]
@@ -76,11 +93,15 @@ impl ServerRuntime {
             let mut vec = rt.0.borrow_mut();
 
             for (i, rt) in &mut vec.iter_mut().enumerate() {
-                if !rt.in_use() {
+                // a runtime whose root slot is retired is left unused
+                if !rt.in_use() && !rt.scope_tree.is_retired() {
                     let id = rt.scope_tree.init(Default::default());
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(scope = ?id, "root scope created");
                     return Scope {
                         rt: ServerRuntime(i as u32),
                         sx: id,
+                        generation: rt.scope_tree.generation(id),
                     };
                 }
             }
@@ -89,8 +110,15 @@ impl ServerRuntime {
             let mut rti = RuntimeInner::new();
             rti.scope_tree.init(Default::default());
             let sx = rti.scope_tree.root();
+            let generation = rti.scope_tree.generation(sx);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(scope = ?sx, "root scope created");
             vec.push(rti);
-            Scope { rt: id, sx }
+            Scope {
+                rt: id,
+                sx,
+                generation,
+            }
         })
     }
 
@@ -0,0 +1,478 @@
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+};
+
+use crate::{
+    arena_tree::NodeId,
+    signals::{SignalId, SignalIndex, SignalType, SignalValue},
+};
+
+use super::{Runtime, RuntimeInner};
+
+/// A snapshot of the scopes of a runtime with their signals, the subscriptions
+/// between the signals and the values of the signals.
+///
+/// It is taken with [Runtime::snapshot()], or recorded after each change made outside of
+/// the signals with [Runtime::record_snapshots()]. The values set by async signals and
+/// timers are such changes, unless the future completes while its signal is run, as the
+/// change then belongs to the snapshot of the change that made the signal run. Consecutive
+/// snapshots can be compared with [diff()](Self::diff()) and the signals that a change went
+/// through with [affected()](Self::affected()).
+///
+/// ```rust
+/// use reactive_signals::{runtimes::{ClientRuntime, Runtime}, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let count = signal!(sc, 1);
+/// let double = signal!(sc, move || count.get() * 2);
+///
+/// sc.runtime().record_snapshots();
+/// count.set(2);
+/// let snapshots = sc.runtime().take_snapshots();
+///
+/// let snapshot = &snapshots[1];
+/// assert_eq!(snapshot.triggers, vec![count.id()]);
+/// assert_eq!(snapshot.affected(), vec![count.id(), double.id()]);
+/// assert_eq!(snapshot.signal(double.id()).unwrap().value.as_deref(), Some("4"));
+///
+/// let diff = snapshots[0].diff(snapshot);
+/// assert_eq!(diff.changed_values.len(), 2);
+/// ```
+///
+/// The values are rendered for the signals created with the [signal!](crate::signal!) macro,
+/// including those derived with the combinators. The signals created by [SignalVec],
+/// [SignalMap], [Store](trait@crate::Store) and [Resource](crate::Resource) are captured with
+/// their subscriptions but always without a value.
+///
+/// [SignalVec]: crate::collections::SignalVec
+/// [SignalMap]: crate::collections::SignalMap
+#[derive(Clone)]
+pub struct Snapshot<RT: Runtime> {
+    /// The signals whose change the snapshot was taken after: the signal that was set
+    /// or the signals that were set during a batch. Empty for a snapshot taken directly.
+    pub triggers: Vec<SignalId<RT>>,
+    /// The scopes, depth first starting with the root scope
+    pub scopes: Vec<ScopeSnapshot<RT>>,
+}
+
+#[derive(Clone)]
+pub struct ScopeSnapshot<RT: Runtime> {
+    /// The index of the scope in the runtime. It is reused once the scope is discarded.
+    pub index: usize,
+    /// The index of the parent scope, none for the root scope
+    pub parent: Option<usize>,
+    pub signals: Vec<SignalSnapshot<RT>>,
+}
+
+#[derive(Clone)]
+pub struct SignalSnapshot<RT: Runtime> {
+    pub id: SignalId<RT>,
+    pub kind: SignalKind,
+    /// The signals that are subscribed to this signal
+    pub listeners: Vec<SignalId<RT>>,
+    /// The [Debug] rendering of the value. It is none when the value doesn't implement
+    /// [Debug], for a signal that wasn't created with the [signal!](crate::signal!) macro
+    /// and for a lazy signal that hasn't been computed.
+    pub value: Option<String>,
+}
+
+/// What kind of signal it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalKind {
+    /// Whether it is a func signal, otherwise it is a data signal
+    pub func: bool,
+    /// Whether the subscribers are only notified when the value changed
+    pub eq: bool,
+    /// Whether the value is only computed when read
+    pub lazy: bool,
+    /// Whether it runs on the server
+    pub server: bool,
+    /// Whether it runs on the client
+    pub client: bool,
+}
+
+/// The differences between two snapshots, see [Snapshot::diff()].
+#[derive(Clone)]
+pub struct SnapshotDiff<RT: Runtime> {
+    pub added_scopes: Vec<usize>,
+    pub removed_scopes: Vec<usize>,
+    pub added_signals: Vec<SignalId<RT>>,
+    pub removed_signals: Vec<SignalId<RT>>,
+    /// The signals in both snapshots with a different value, with the previous and the next value
+    pub changed_values: Vec<(SignalId<RT>, Option<String>, Option<String>)>,
+    /// The subscriptions added, as (signal, listener)
+    pub added_edges: Vec<(SignalId<RT>, SignalId<RT>)>,
+    /// The subscriptions removed, as (signal, listener)
+    pub removed_edges: Vec<(SignalId<RT>, SignalId<RT>)>,
+}
+
+impl<RT: Runtime> Snapshot<RT> {
+    pub fn signals(&self) -> impl Iterator<Item = &SignalSnapshot<RT>> {
+        self.scopes.iter().flat_map(|scope| scope.signals.iter())
+    }
+
+    pub fn signal(&self, id: SignalId<RT>) -> Option<&SignalSnapshot<RT>> {
+        self.signals().find(|signal| signal.id == id)
+    }
+
+    /// The triggers and the signals that are subscribed to them,
+    /// directly or through other signals, sorted.
+    pub fn affected(&self) -> Vec<SignalId<RT>> {
+        let listeners: BTreeMap<_, _> = self
+            .signals()
+            .map(|signal| (signal.id, &signal.listeners))
+            .collect();
+
+        let mut affected = BTreeSet::new();
+        let mut pending = self.triggers.clone();
+        while let Some(id) = pending.pop() {
+            if affected.insert(id) {
+                pending.extend(listeners.get(&id).into_iter().flat_map(|l| l.iter()));
+            }
+        }
+        affected.into_iter().collect()
+    }
+
+    /// What changed from this snapshot to the next one.
+    pub fn diff(&self, next: &Snapshot<RT>) -> SnapshotDiff<RT> {
+        let (prev_scopes, next_scopes) = (self.scope_indexes(), next.scope_indexes());
+        let (prev_signals, next_signals) = (self.values(), next.values());
+        let (prev_edges, next_edges) = (self.edges(), next.edges());
+
+        SnapshotDiff {
+            added_scopes: next_scopes.difference(&prev_scopes).copied().collect(),
+            removed_scopes: prev_scopes.difference(&next_scopes).copied().collect(),
+            added_signals: (next_signals.keys())
+                .filter(|id| !prev_signals.contains_key(id))
+                .copied()
+                .collect(),
+            removed_signals: (prev_signals.keys())
+                .filter(|id| !next_signals.contains_key(id))
+                .copied()
+                .collect(),
+            changed_values: (prev_signals.iter())
+                .filter_map(|(id, prev)| {
+                    let next = next_signals.get(id)?;
+                    (prev != next).then(|| (*id, (*prev).clone(), (*next).clone()))
+                })
+                .collect(),
+            added_edges: next_edges.difference(&prev_edges).copied().collect(),
+            removed_edges: prev_edges.difference(&next_edges).copied().collect(),
+        }
+    }
+
+    fn scope_indexes(&self) -> BTreeSet<usize> {
+        self.scopes.iter().map(|scope| scope.index).collect()
+    }
+
+    fn values(&self) -> BTreeMap<SignalId<RT>, &Option<String>> {
+        self.signals().map(|s| (s.id, &s.value)).collect()
+    }
+
+    /// The subscriptions, as (signal, listener)
+    pub(super) fn edges(&self) -> BTreeSet<(SignalId<RT>, SignalId<RT>)> {
+        self.signals()
+            .flat_map(|s| s.listeners.iter().map(|listener| (s.id, *listener)))
+            .collect()
+    }
+}
+
+impl<RT: Runtime> SnapshotDiff<RT> {
+    pub fn is_empty(&self) -> bool {
+        self.added_scopes.is_empty()
+            && self.removed_scopes.is_empty()
+            && self.added_signals.is_empty()
+            && self.removed_signals.is_empty()
+            && self.changed_values.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+impl<RT: Runtime> RuntimeInner<RT> {
+    /// Captures the scopes and signals of the runtime, see [Snapshot].
+    pub fn snapshot(&self, rt: RT) -> Snapshot<RT> {
+        self.snapshot_after(rt, Vec::new())
+    }
+
+    fn snapshot_after(&self, rt: RT, triggers: Vec<SignalId<RT>>) -> Snapshot<RT> {
+        let mut scopes = Vec::new();
+        if self.in_use() {
+            self.snapshot_scope(rt, self.scope_tree.root(), &mut scopes);
+        }
+        Snapshot { triggers, scopes }
+    }
+
+    fn snapshot_scope(&self, rt: RT, sx: NodeId, scopes: &mut Vec<ScopeSnapshot<RT>>) {
+        let signals = self.scope_tree[sx]
+            .vec_ref()
+            .iter()
+            .enumerate()
+            .map(|(index, signal)| {
+                let (func, value) = match &signal.value {
+                    SignalValue::Data(value) => (false, value),
+                    SignalValue::Func(func) => (true, &func.value),
+                    #[cfg(debug_assertions)]
+                    SignalValue::Reuse => panic!("BUG: using a reused signal"),
+                };
+                let ty = value.try_with_any(|value| self.value_types.get((*value).type_id()));
+                let ty = ty.flatten();
+                SignalSnapshot {
+                    id: SignalId {
+                        id: SignalIndex::new(index, false),
+                        sx,
+                        rt,
+                    },
+                    kind: SignalKind {
+                        func,
+                        ..ty.map_or(ValueType::UNKNOWN, |ty| ty.kind)
+                    },
+                    listeners: (0..signal.listeners.len())
+                        .map(|i| signal.listeners.get(i))
+                        .collect(),
+                    value: ty
+                        .and_then(|ty| ty.debug)
+                        .and_then(|debug| value.try_with_any(debug).flatten()),
+                }
+            })
+            .collect();
+
+        scopes.push(ScopeSnapshot {
+            index: sx.index(),
+            parent: self.scope_tree.nodes[sx.index()].parent.map(NodeId::index),
+            signals,
+        });
+        for child in self.scope_tree.children(sx) {
+            self.snapshot_scope(rt, child, scopes);
+        }
+    }
+
+    /// Starts recording a snapshot after each change made outside of the
+    /// signals, beginning with one of the current state.
+    pub(crate) fn record_snapshots(&self, rt: RT) {
+        let snapshots = self
+            .snapshots
+            .take()
+            .unwrap_or_else(|| vec![self.snapshot(rt)]);
+        self.snapshots.set(Some(snapshots));
+    }
+
+    /// Stops recording and returns the recorded snapshots
+    pub(crate) fn take_snapshots(&self) -> Vec<Snapshot<RT>> {
+        self.snapshots.take().unwrap_or_default()
+    }
+
+    /// Records a snapshot, if recording, when the changes were made outside of the
+    /// propagation of a change. This includes the values of the async signals and timers
+    /// that their tasks set when they are polled by the executor.
+    pub(crate) fn snapshot_changes(&self, rt: RT, triggers: &[SignalId<RT>]) {
+        if triggers.is_empty() || self.is_propagating() {
+            return;
+        }
+        if let Some(mut snapshots) = self.snapshots.take() {
+            snapshots.push(self.snapshot_after(rt, triggers.to_vec()));
+            self.snapshots.set(Some(snapshots));
+        }
+    }
+}
+
+/// The kinds of the signal value types of a runtime and how to render their values,
+/// sorted by [TypeId].
+#[derive(Default)]
+pub(crate) struct ValueTypes(Cell<Vec<ValueType>>);
+
+#[derive(Clone, Copy)]
+struct ValueType {
+    type_id: TypeId,
+    kind: SignalKind,
+    debug: Option<fn(&dyn Any) -> Option<String>>,
+}
+
+impl ValueType {
+    const UNKNOWN: SignalKind = SignalKind {
+        func: false,
+        eq: false,
+        lazy: false,
+        server: true,
+        client: true,
+    };
+}
+
+impl ValueTypes {
+    pub(crate) fn register<T: SignalType>(&self) {
+        self.update::<T>(|_| {});
+    }
+
+    pub(crate) fn register_debug<T: SignalType>(&self)
+    where
+        T::Inner: Debug,
+    {
+        self.update::<T>(|ty| ty.debug = Some(debug_value::<T>));
+    }
+
+    fn update<T: SignalType>(&self, f: impl FnOnce(&mut ValueType)) {
+        let type_id = TypeId::of::<T>();
+        let mut types = self.0.take();
+        let pos = match types.binary_search_by_key(&type_id, |ty| ty.type_id) {
+            Ok(pos) => pos,
+            Err(pos) => {
+                let kind = SignalKind {
+                    func: false,
+                    eq: T::EQ,
+                    lazy: T::LAZY,
+                    server: T::SERVER,
+                    client: T::CLIENT,
+                };
+                let debug = None;
+                types.insert(
+                    pos,
+                    ValueType {
+                        type_id,
+                        kind,
+                        debug,
+                    },
+                );
+                pos
+            }
+        };
+        f(&mut types[pos]);
+        self.0.set(types);
+    }
+
+    fn get(&self, type_id: TypeId) -> Option<ValueType> {
+        let types = self.0.take();
+        let ty = types
+            .binary_search_by_key(&type_id, |ty| ty.type_id)
+            .ok()
+            .map(|pos| types[pos]);
+        self.0.set(types);
+        ty
+    }
+}
+
+fn debug_value<T: SignalType>(value: &dyn Any) -> Option<String>
+where
+    T::Inner: Debug,
+{
+    let value = value.downcast_ref::<T>()?;
+    (!value.is_stale()).then(|| format!("{:?}", value.inner()))
+}
+
+#[test]
+fn test_snapshot() {
+    use crate::{runtimes::TestClientRuntime, signal};
+
+    struct NoDebug;
+
+    let sc = TestClientRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let text_sig = signal!(sc, move || format!("n{}", num_sig.get()));
+    let no_debug_sig = signal!(sc, NoDebug);
+    let lazy_sig = signal!(sc, lazy, move || num_sig.get() + 1);
+    let server_sig = signal!(sc, server, move || num_sig.get());
+
+    let rt = sc.runtime();
+    rt.record_snapshots();
+    let child = sc.new_child();
+    let child_sig = signal!(child, move || text_sig.cloned().len());
+    sc.batch(|| {
+        num_sig.set(10);
+        no_debug_sig.set(NoDebug);
+    });
+    let snapshots = rt.take_snapshots();
+    assert_eq!(snapshots.len(), 2);
+
+    let snapshot = &snapshots[1];
+    assert_eq!(snapshot.triggers, vec![num_sig.id(), no_debug_sig.id()]);
+    assert_eq!(
+        snapshot.affected(),
+        vec![
+            num_sig.id(),
+            text_sig.id(),
+            no_debug_sig.id(),
+            server_sig.id(),
+            child_sig.id()
+        ]
+    );
+    let scopes: Vec<_> = snapshot
+        .scopes
+        .iter()
+        .map(|s| (s.index, s.parent))
+        .collect();
+    assert_eq!(scopes, vec![(0, None), (1, Some(0))]);
+
+    let signal = |id| snapshot.signal(id).unwrap();
+    assert_eq!(signal(text_sig.id()).value.as_deref(), Some("\"n10\""));
+    assert_eq!(signal(no_debug_sig.id()).value, None);
+    assert_eq!(signal(lazy_sig.id()).value, None);
+    assert_eq!(signal(server_sig.id()).value.as_deref(), Some("10"));
+    assert_eq!(signal(num_sig.id()).listeners.len(), 2);
+
+    let kind = signal(num_sig.id()).kind;
+    assert!(!kind.func && kind.eq && kind.server && kind.client);
+    let kind = signal(lazy_sig.id()).kind;
+    assert!(kind.func && kind.lazy);
+    let kind = signal(server_sig.id()).kind;
+    assert!(kind.func && kind.server && !kind.client);
+
+    let diff = snapshots[0].diff(snapshot);
+    assert_eq!(diff.added_scopes, vec![1]);
+    assert_eq!(diff.added_signals, vec![child_sig.id()]);
+    assert_eq!(diff.added_edges, vec![(text_sig.id(), child_sig.id())]);
+    assert_eq!(
+        diff.changed_values,
+        vec![
+            (num_sig.id(), Some("1".to_string()), Some("10".to_string())),
+            (
+                text_sig.id(),
+                Some("\"n1\"".to_string()),
+                Some("\"n10\"".to_string())
+            ),
+            (
+                server_sig.id(),
+                Some("1".to_string()),
+                Some("10".to_string())
+            ),
+        ]
+    );
+    assert!(snapshot.diff(snapshot).is_empty());
+
+    // sets made by the signals don't record snapshots
+    rt.record_snapshots();
+    let copy_sig = signal!(sc, 0);
+    signal!(sc, move || copy_sig.set(num_sig.get()));
+    num_sig.set(4);
+    assert_eq!(rt.take_snapshots().len(), 2);
+}
+
+#[test]
+fn test_snapshot_async() {
+    use crate::{runtimes::TestClientRuntime, signal, tests::Deferred};
+    use std::{rc::Rc, time::Duration};
+
+    let sc = TestClientRuntime::new_root_scope();
+    let deferred = Rc::new(Deferred::new());
+    let async_sig = signal!(sc, clone: deferred, move async || deferred.wait().await);
+    let timer_sig = signal!(sc, 10 ms, move async |interval| {
+        *interval = None;
+        1
+    });
+
+    let rt = sc.runtime();
+    rt.record_snapshots();
+    deferred.send(5);
+    rt.advance(Duration::from_millis(10));
+
+    // the values set by the tasks are changes made outside of the signals
+    let snapshots = rt.take_snapshots();
+    let triggers: Vec<_> = snapshots.iter().map(|s| s.triggers.clone()).collect();
+    assert_eq!(
+        triggers,
+        vec![vec![], vec![async_sig.id()], vec![timer_sig.id()]]
+    );
+    let value = snapshots[1].signal(async_sig.id()).unwrap().value.clone();
+    assert_eq!(value.as_deref(), Some("Some(5)"));
+}
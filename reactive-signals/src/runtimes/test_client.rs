@@ -1,6 +1,7 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::Duration};
 
-use super::{Runtime, RuntimeInner, Scope};
+use super::{timers, Runtime, RuntimeInner, Scope};
+use crate::ReactiveError;
 
 thread_local! {
   pub static RUNTIME_POOL: TestClientRuntimePool = Default::default();
@@ -27,6 +28,9 @@ thread_local! {
 /// }
 /// ```
 ///
+/// The timer signals use a virtual clock that only changes when it is advanced
+/// with [advance()](Self::advance()), so that they can be tested without waiting.
+///
 /// See [runtimes](super) for full documentation.
 ///
 #[derive(Default, Clone, Copy)]
@@ -65,22 +69,61 @@ impl Runtime for TestClientRuntime {
             f(rt)
         })
     }
+
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, ReactiveError>
+    where
+        F: FnOnce(&RuntimeInner<TestClientRuntime>) -> Result<T, ReactiveError>,
+    {
+        RUNTIME_POOL.with(|pool| {
+            let pool = pool
+                .0
+                .try_borrow()
+                .map_err(|_| ReactiveError::ReentrantBorrow)?;
+            let rt = pool
+                .get(self.0 as usize)
+                .ok_or(ReactiveError::WrongRuntime)?;
+            f(rt)
+        })
+    }
 }
 
 #[derive(Default)]
 pub struct TestClientRuntimePool(RefCell<Vec<RuntimeInner<TestClientRuntime>>>);
 
 impl TestClientRuntime {
+    /// Advances the virtual clock by the duration and runs the timer signals that are
+    /// due, in the order of their times. A timer signal sees the clock at its own time,
+    /// so that an interval runs as many times as it would have during the duration.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::TestClientRuntime, signal};
+    /// use std::time::Duration;
+    ///
+    /// let sc = TestClientRuntime::new_root_scope();
+    /// let ticks = signal!(sc, 0);
+    /// signal!(sc, 100 ms, move async |_interval| ticks.update(|t| *t += 1));
+    ///
+    /// sc.runtime().advance(Duration::from_millis(350));
+    /// assert_eq!(ticks.get(), 3);
+    /// ```
+    pub fn advance(&self, duration: Duration) {
+        timers::advance(*self, duration)
+    }
+
     pub fn new_root_scope() -> Scope<TestClientRuntime> {
         RUNTIME_POOL.with(|rt| {
             let mut vec = rt.0.borrow_mut();
 
             for (i, rt) in &mut vec.iter_mut().enumerate() {
-                if !rt.in_use() {
+                // a runtime whose root slot is retired is left unused
+                if !rt.in_use() && !rt.scope_tree.is_retired() {
                     let id = rt.scope_tree.init(Default::default());
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(scope = ?id, "root scope created");
                     return Scope {
                         rt: TestClientRuntime(i as u32),
                         sx: id,
+                        generation: rt.scope_tree.generation(id),
                     };
                 }
             }
@@ -89,8 +132,15 @@ impl TestClientRuntime {
             let mut rti = RuntimeInner::new();
             rti.scope_tree.init(Default::default());
             let sx = rti.scope_tree.root();
+            let generation = rti.scope_tree.generation(sx);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(scope = ?sx, "root scope created");
             vec.push(rti);
-            Scope { rt: id, sx }
+            Scope {
+                rt: id,
+                sx,
+                generation,
+            }
         })
     }
 
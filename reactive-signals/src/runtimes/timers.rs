@@ -0,0 +1,176 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use super::Runtime;
+
+/// The clock of a runtime and the futures sleeping until a time of it.
+///
+/// The clock doesn't read the time of the system, which isn't available on all targets,
+/// such as `wasm32-unknown-unknown`. It is set to the time given to
+/// [run_timers()](super::ClientRuntime::run_timers()) or advanced by
+/// [advance()](super::TestClientRuntime::advance()), and never goes back.
+#[derive(Default)]
+pub(crate) struct Timers {
+    now: Cell<Duration>,
+    /// The sleeping futures. A future that is dropped is no longer upgradable.
+    sleeping: Cell<Vec<Weak<Sleeper>>>,
+}
+
+/// The deadline of a sleeping future and the waker of the last task that polled it
+struct Sleeper {
+    deadline: Duration,
+    waker: Cell<Option<Waker>>,
+}
+
+impl Timers {
+    pub(crate) fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn set_now(&self, now: Duration) {
+        self.now.set(now.max(self.now.get()));
+    }
+
+    fn sleep(&self, sleeper: &Rc<Sleeper>) {
+        let mut sleeping = self.sleeping.take();
+        sleeping.retain(|s| s.strong_count() > 0);
+        sleeping.push(Rc::downgrade(sleeper));
+        self.sleeping.set(sleeping);
+    }
+
+    /// The earliest time a future is sleeping until
+    fn next_deadline(&self) -> Option<Duration> {
+        let sleeping = self.sleeping.take();
+        let next = sleeping
+            .iter()
+            .filter_map(|s| Some(s.upgrade()?.deadline))
+            .min();
+        self.sleeping.set(sleeping);
+        next
+    }
+
+    /// Removes the futures sleeping until the time or an earlier one,
+    /// and the dropped ones, and returns the wakers of the first.
+    fn take_due(&self, now: Duration) -> Vec<Waker> {
+        let mut due = Vec::new();
+        let mut sleeping = self.sleeping.take();
+        sleeping.retain(|s| match s.upgrade() {
+            Some(sleeper) if sleeper.deadline <= now => {
+                due.extend(sleeper.waker.take());
+                false
+            }
+            Some(_) => true,
+            None => false,
+        });
+        self.sleeping.set(sleeping);
+        due
+    }
+}
+
+/// Sets the clock to the time, wakes the futures whose time has come and
+/// returns the time until the next one is to be woken, if any.
+pub(crate) fn wake_due<RT: Runtime>(rt: RT, now: Duration) -> Option<Duration> {
+    let due = rt.with_ref(|rt| {
+        rt.timers.set_now(now);
+        rt.timers.take_due(rt.timers.now())
+    });
+    due.into_iter().for_each(Waker::wake);
+    rt.with_ref(|rt| {
+        let now = rt.timers.now();
+        let next = rt.timers.next_deadline()?;
+        Some(next.saturating_sub(now))
+    })
+}
+
+/// Advances the clock, waking the sleeping futures in the order of their
+/// times. The clock is set to the time of each future when it is woken.
+pub(crate) fn advance<RT: Runtime>(rt: RT, duration: Duration) {
+    let until = rt.with_ref(|rt| rt.timers.now() + duration);
+    while let Some(next) = rt.with_ref(|rt| rt.timers.next_deadline()) {
+        if next > until {
+            break;
+        }
+        wake_due(rt, next);
+    }
+    rt.with_ref(|rt| rt.timers.set_now(until));
+}
+
+/// A future that completes when the clock of the runtime has reached its deadline.
+///
+/// It is always pending the first time it is polled, so that the task sleeping
+/// returns to the executor even when its deadline has already passed. It is only
+/// registered once with the timers, with the waker of the last poll, and stops
+/// being woken when it is dropped.
+pub(crate) struct Sleep<RT> {
+    rt: RT,
+    sleeper: Rc<Sleeper>,
+    registered: bool,
+}
+
+impl<RT: Runtime> Sleep<RT> {
+    pub(crate) fn new(rt: RT, duration: Duration) -> Self {
+        let deadline = rt.with_ref(|rt| rt.timers.now()) + duration;
+        Self {
+            rt,
+            sleeper: Rc::new(Sleeper {
+                deadline,
+                waker: Cell::new(None),
+            }),
+            registered: false,
+        }
+    }
+}
+
+// nothing in Sleep is pinned
+impl<RT> Unpin for Sleep<RT> {}
+
+impl<RT: Runtime> Future for Sleep<RT> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        this.rt.with_ref(|rt| {
+            if this.registered && rt.timers.now() >= this.sleeper.deadline {
+                return Poll::Ready(());
+            }
+            this.sleeper.waker.set(Some(cx.waker().clone()));
+            if !this.registered {
+                rt.timers.sleep(&this.sleeper);
+                this.registered = true;
+            }
+            Poll::Pending
+        })
+    }
+}
+
+#[test]
+fn test_sleep_wakers() {
+    use super::TestClientRuntime;
+
+    let sc = TestClientRuntime::new_root_scope();
+    let rt = sc.runtime();
+    let sleeping = || rt.with_ref(|rt| rt.timers.next_deadline());
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut sleep = Sleep::new(rt, Duration::from_millis(10));
+    let mut other = Sleep::new(rt, Duration::from_millis(20));
+    assert!(Pin::new(&mut other).poll(&mut cx).is_pending());
+    for _ in 0..3 {
+        assert!(Pin::new(&mut sleep).poll(&mut cx).is_pending());
+    }
+    // the future that was polled three times is registered once
+    let sleepers = rt.with_ref(|rt| rt.timers.take_due(Duration::from_millis(10)));
+    assert_eq!(sleepers.len(), 1);
+
+    // a dropped future is no longer sleeping
+    assert_eq!(sleeping(), Some(Duration::from_millis(20)));
+    drop(other);
+    assert_eq!(sleeping(), None);
+    sc.discard();
+}
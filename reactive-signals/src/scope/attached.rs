@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use crate::{
+    runtimes::Runtime,
+    signals::{SignalIndex, SignalType},
+    Scope, Signal,
+};
+
+/// The index of a signal of type `T` attached to a scope with `#[derive(Scoped)]`.
+/// Used by the scope wrappers, which hold one for each attached value.
+#[doc(hidden)]
+pub struct Attached<T> {
+    index: SignalIndex,
+    ty: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Attached<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Attached<T> {}
+
+impl<T: SignalType> Attached<T> {
+    pub fn new<RT: Runtime>(sig: Signal<T, RT>) -> Self {
+        Self {
+            index: sig.index(),
+            ty: PhantomData,
+        }
+    }
+
+    /// The signal in the scope it was attached to
+    pub fn signal<RT: Runtime>(&self, sx: Scope<RT>) -> Signal<T, RT> {
+        Signal::from_index(sx, self.index)
+    }
+}
+
+#[test]
+fn test_scoped() {
+    use crate::{runtimes::ServerRuntime, signal, tests::StringStore, Scoped};
+    use std::rc::Rc;
+
+    #[derive(Scoped, Clone, Copy, PartialEq)]
+    #[scoped(Scope)]
+    struct UserId(u32);
+
+    #[derive(Scoped)]
+    #[scoped(UserIdScope, name = "SessionScope", no_eq)]
+    struct Session(Rc<str>);
+
+    let sc = ServerRuntime::new_root_scope();
+    let sc = UserId(1).attach_to(sc);
+    let child = sc.new_child();
+    let sc = Session("abc".into()).attach_to(sc);
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || {
+        let id = sc.user_id().get().0;
+        output.push(format!("{id}: {}", sc.session().with(|s| s.0.clone())))
+    });
+
+    sc.user_id().set(UserId(2));
+    sc.user_id().set(UserId(2));
+    sc.session().set(Session("def".into()));
+    assert_eq!(output.values(), "1: abc, 2: abc, 2: def");
+
+    // the attached signals belong to the scope they were attached to
+    signal!(child, 5u8);
+    assert_eq!(sc.user_id().get().0, 2);
+}
@@ -0,0 +1,55 @@
+use crate::{runtimes::Runtime, signals::propagate_changes};
+
+/// Defers the propagation of signal changes until it is dropped. When dropped,
+/// all the signals that changed while it was alive are propagated together, so
+/// that a signal depending on several of them only runs once.
+///
+/// Batches can be nested, in which case the propagation happens when the
+/// outermost batch ends.
+///
+/// It is created with [Scope::batch_guard()](crate::Scope::batch_guard()).
+/// See [Scope::batch()](crate::Scope::batch()) for the closure based version.
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal};
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let first = signal!(sc, "Ada");
+/// let last = signal!(sc, "Lovelace");
+/// let full_name = signal!(sc, move || format!("{} {}", first.get(), last.get()));
+///
+/// let guard = sc.batch_guard();
+/// first.set("Grace");
+/// last.set("Hopper");
+/// // nothing has been propagated yet
+/// assert_eq!(full_name.cloned(), "Ada Lovelace");
+///
+/// drop(guard);
+/// assert_eq!(full_name.cloned(), "Grace Hopper");
+/// ```
+pub struct BatchGuard<RT: Runtime> {
+    rt: RT,
+}
+
+impl<RT: Runtime> BatchGuard<RT> {
+    pub(crate) fn new(rt: RT) -> Self {
+        rt.with_ref(|rt| rt.start_batch());
+        Self { rt }
+    }
+}
+
+impl<RT: Runtime> Drop for BatchGuard<RT> {
+    fn drop(&mut self) {
+        self.rt.with_ref(|rt| {
+            if let Some(changes) = rt.end_batch() {
+                // don't run any signals when unwinding
+                if !std::thread::panicking() {
+                    propagate_changes(rt, &changes);
+                    rt.check_cycle();
+                    #[cfg(feature = "snapshots")]
+                    rt.snapshot_changes(self.rt, &changes);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,51 @@
+use std::cell::Cell;
+
+use crate::{runtimes::Runtime, signals::SignalId};
+
+struct Cleanup<RT: Runtime> {
+    /// The func signal that registered the cleanup, or none
+    /// if it belongs to the scope
+    owner: Option<SignalId<RT>>,
+    func: Box<dyn FnOnce()>,
+}
+
+/// The cleanup callbacks registered in a scope, in registration order.
+pub(crate) struct Cleanups<RT: Runtime>(Cell<Vec<Cleanup<RT>>>);
+
+impl<RT: Runtime> Default for Cleanups<RT> {
+    fn default() -> Self {
+        Self(Cell::new(Vec::new()))
+    }
+}
+
+impl<RT: Runtime> std::fmt::Debug for Cleanups<RT> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cleanups = self.0.take();
+        let len = cleanups.len();
+        self.0.set(cleanups);
+        write!(f, "Cleanups({len})")
+    }
+}
+
+impl<RT: Runtime> Cleanups<RT> {
+    pub(crate) fn add(&self, owner: Option<SignalId<RT>>, func: Box<dyn FnOnce()>) {
+        let mut cleanups = self.0.take();
+        cleanups.push(Cleanup { owner, func });
+        self.0.set(cleanups);
+    }
+
+    /// Removes and returns the cleanups registered by the signal
+    pub(crate) fn take_for(&self, signal: SignalId<RT>) -> Vec<Box<dyn FnOnce()>> {
+        let cleanups = self.0.take();
+        let (taken, kept) = cleanups
+            .into_iter()
+            .partition::<Vec<_>, _>(|c| c.owner == Some(signal));
+        self.0.set(kept);
+        taken.into_iter().map(|c| c.func).collect()
+    }
+
+    /// Removes and returns all cleanups
+    pub(crate) fn take_all(&self) -> Vec<Box<dyn FnOnce()>> {
+        self.0.take().into_iter().map(|c| c.func).collect()
+    }
+}
@@ -0,0 +1,38 @@
+use std::{any::Any, cell::Cell};
+
+/// The context values provided by a scope, at most one per type.
+#[derive(Default)]
+pub(crate) struct Contexts(Cell<Vec<Box<dyn Any>>>);
+
+impl std::fmt::Debug for Contexts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let contexts = self.0.take();
+        let len = contexts.len();
+        self.0.set(contexts);
+        write!(f, "Contexts({len})")
+    }
+}
+
+impl Contexts {
+    /// Adds the value, replacing the one of the same type if any
+    pub(crate) fn provide<T: 'static>(&self, value: T) {
+        let mut contexts = self.0.take();
+        match contexts.iter_mut().find(|c| c.is::<T>()) {
+            Some(context) => *context = Box::new(value),
+            None => contexts.push(Box::new(value)),
+        }
+        self.0.set(contexts);
+    }
+
+    pub(crate) fn get<T: Clone + 'static>(&self) -> Option<T> {
+        let contexts = self.0.take();
+        let value = contexts.iter().find_map(|c| c.downcast_ref::<T>()).cloned();
+        self.0.set(contexts);
+        value
+    }
+
+    /// Removes and returns all values
+    pub(crate) fn take_all(&self) -> Vec<Box<dyn Any>> {
+        self.0.take()
+    }
+}
@@ -1,9 +1,17 @@
 #[cfg(test)]
 mod tests;
 
+mod attached;
+mod batch;
+mod cleanup;
+mod context;
 #[allow(clippy::module_inception)]
 mod scope;
 mod scope_inner;
 
+#[doc(hidden)]
+pub use attached::Attached;
+pub use batch::BatchGuard;
+pub(crate) use scope::RunningSignalGuard;
 pub use scope::Scope;
 pub(crate) use scope_inner::ScopeInner;
@@ -1,6 +1,9 @@
 use crate::arena_tree::NodeId;
+use crate::signals::SignalId;
 use crate::Runtime;
 
+use super::BatchGuard;
+
 ///
 /// [Signal](crate::Signal)s are created in scopes and can only be deleted by
 /// discarding the scope.
@@ -11,88 +14,328 @@ use crate::Runtime;
 /// When calling a Scope's [discard()](Self::discard()) function, the Scope and it's child scopes are discarded
 /// together with their signals.
 ///
-/// Internally, a Scope is really just a u16 index into an arena based tree which contains the
+/// Internally, a Scope is really just a u16 index (u32 with the `large-ids` feature) into an arena based tree which contains the
 /// full ScopeInner data (not exposed in the api doc). The Scope implements [Copy] which makes it
 /// much easier to use in closures.
 ///
-/// There can be a maximum of 65k Scopes.
-///
-/// ## Typed attached data
+/// The Scope also holds the generation of the tree slot it was created in. A discarded Scope's slot
+/// is reused by new scopes, so using a discarded Scope, or a [Signal](crate::Signal) created in it,
+/// panics instead of accessing the data of the scope that now occupies the slot. A slot is retired
+/// once its generation has reached 65535, rather than wrapping around, so a stale Scope is always
+/// detected. For the same reason a runtime can create its root scope at most 65535 times: a
+/// [ClientRuntime](crate::runtimes::ClientRuntime) panics after that, whereas the pooled runtimes
+/// continue in a new runtime.
 ///
-/// > _**This has not been implemented!** A proof of concept has been done but the exact
-/// > details of how the api will work are not cemented yet._
+/// There can be a maximum of 65k Scopes with up to 32k signals each. Enable the `large-ids` feature
+/// to raise the limits to 4 billion Scopes and 2 billion signals per Scope.
 ///
+/// ## Typed attached data
 ///
 /// It is possible to attach data to a Scope and then, in a type-safe and performant manner, access it.
 /// When attached to a Scope the data gets transformed into a Signal which can be retrieved
 /// with a function named as the data struct but snake-cased.
 ///
-/// You can add several nested data values to a scope. The cost of adding one is 2 bytes added
-/// to the scope id which is the vector index of the signal plus the cost of the Signal added to
-/// the ScopeInner.
+/// The data type derives [Scoped](derive@crate::Scoped) with the type of the scope it is attached
+/// to as argument. That generates an `attach_to(scope)` function which returns a scope wrapper,
+/// named as the data struct with a `Scope` suffix unless a `name` is given. The wrapper can be
+/// used wherever its scope can, including in the [signal!](crate::signal!) macro. The data is
+/// stored in an [EqData](crate::types::EqData) signal, use the `no_eq` argument for a type
+/// that doesn't implement [PartialEq].
+///
+/// You can add several nested data values to a scope. The cost of adding one is 2 bytes
+/// (4 with the `large-ids` feature) added to the scope wrapper, which is the vector index of the
+/// signal, plus the cost of the Signal added to the ScopeInner.
+///
+/// ```rust
+/// use reactive_signals::{runtimes::{ClientRuntime, Runtime}, signal, types::EqData, Scope, Scoped, Signal};
 ///
-/// ```ignore
 /// // derive using Scoped with the input scope type as argument
 /// // the generated scope wrapper is named MyCounterScope
-/// #[derive(Scoped(Scope), Clone, Copy)]
+/// #[derive(Scoped, Clone, Copy, PartialEq)]
+/// #[scoped(Scope)]
 /// struct MyCounter(u8);
 ///
 /// // a derive with the name argument as well
-/// #[derive(Scoped(MyCounterScope, name = "BaseScope"), Clone)]
+/// #[derive(Scoped, Clone, PartialEq)]
+/// #[scoped(MyCounterScope, name = "BaseScope")]
 /// struct MyGreeting(String);
 ///
-/// fn some_func<RT: Runtime>(sc: Scope<RT>) {
+/// fn some_func<RT: Runtime + 'static>(sc: Scope<RT>) -> BaseScope<RT> {
 ///     // create your data
-///     let count = MyCounter(0);
+///     let count = MyCounter(2);
 ///
 ///     // attach it to the scope (type annotations not necessary)
-///     let sc: MyCounterScope<RT: Runtime> = count.attach_to(sc);
-///     
-///     // The MyCount instance can be accessed as a signal (type annotations not necessary)
-///     let count_signal: Signal<EqData<i32>, RT> = sc.my_counter();
-///     
+///     let sc: MyCounterScope<RT> = count.attach_to(sc);
+///
+///     // The MyCounter instance can be accessed as a signal (type annotations not necessary)
+///     let count_signal: Signal<EqData<MyCounter>, RT> = sc.my_counter();
+///
 ///     // Create a MyGreeting and attach it to the MyCounterScope
-///     let sc: BaseScope<RT: Runtime> = MyGreeting("hi ".to_string()).attach_to(sc);
+///     let sc: BaseScope<RT> = MyGreeting(" hi ".to_string()).attach_to(sc);
 ///
 ///     next_func(sc);
+///     sc
 /// }
 ///
 /// // The scope is passed as a typed parameter
-/// fn next_func<RT: Runtime>(sc: BaseScope<RT>) {
+/// fn next_func<RT: Runtime + 'static>(sc: BaseScope<RT>) {
 ///     // the scoped data can be modified
-///     sc.my_greeting().update(|s| *s = *s.trim());
+///     sc.my_greeting().update(|s| s.0 = s.0.trim().to_string());
 ///
 ///     signal!(sc, move || {
-///         sc.my_greeting().with(|greet| println!("{greet} {} times", sc.my_counter().get()))
+///         sc.my_greeting().with(|greet| format!("{} {} times", greet.0, sc.my_counter().get().0))
 ///     });
 /// }
+///
+/// let sc = some_func(ClientRuntime::new_root_scope());
+/// assert_eq!(sc.my_greeting().cloned().0, "hi");
 /// ```
 ///
 #[derive(Copy, Clone)]
 pub struct Scope<RT: Runtime> {
     pub(crate) sx: NodeId,
     pub(crate) rt: RT,
+    pub(crate) generation: u16,
 }
 
 impl<RT: Runtime> Scope<RT> {
+    /// The runtime the scope belongs to.
+    pub fn runtime(&self) -> RT {
+        self.rt
+    }
+
     pub fn new_child(&self) -> Self {
         self.rt.with_mut(|rt| {
+            rt.check_scope(self.sx, self.generation);
             let sx = rt.scope_tree.add_child(self.sx, Default::default());
-            Self { sx, rt: self.rt }
+            let generation = rt.scope_tree.generation(sx);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(scope = ?sx, parent = ?self.sx, "scope created");
+            Self {
+                sx,
+                rt: self.rt,
+                generation,
+            }
         })
     }
 
+    /// Discards the scope together with its child scopes and their signals.
+    ///
+    /// The cleanups registered with [on_discard()](Self::on_discard()) and
+    /// [on_cleanup()](Self::on_cleanup()) are run first, those of the child scopes
+    /// before those of their parent.
     pub fn discard(self) {
-        self.rt.with_mut(|rt| {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(scope = ?self.sx, "scope discarded");
+        let (cleanups, contexts) = self.rt.with_mut(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            rt.scope_tree.iter_mut_from(self.sx).fold(
+                (Vec::new(), Vec::new()),
+                |tree, (mut cleanups, mut contexts), node| {
+                    cleanups.extend(tree[node].cleanups.take_all());
+                    contexts.extend(tree[node].contexts.take_all());
+                    (cleanups, contexts)
+                },
+            )
+        });
+        self.untrack(|| cleanups.into_iter().for_each(|f| f()));
+        // dropped outside of the runtime borrow as they may hold signals
+        drop(contexts);
+
+        let tasks = self.rt.with_mut(|rt| {
             let is_root = rt.scope_tree.root() == self.sx;
             if is_root {
-                self.rt.discard();
+                rt.discard()
             } else {
                 let discarded = rt.scope_tree.discard(self.sx, |s| s.reuse());
                 rt.scope_tree
                     .iter_mut_from(rt.scope_tree.root())
                     .for_each(|tree, node| tree[node].remove_scopes(&discarded));
+                rt.remove_batched_scopes(&discarded);
+                rt.remove_polled_scopes(&discarded);
+                Vec::new()
+            }
+        });
+        // dropped outside of the runtime borrow as they may use signals
+        drop(tasks);
+    }
+
+    /// Registers a function that is run when the scope is discarded.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// let root = ClientRuntime::new_root_scope();
+    /// let log = signal!(root, Vec::<&str>::new());
+    ///
+    /// let parent = root.new_child();
+    /// let child = parent.new_child();
+    /// parent.on_discard(move || log.update(|l| l.push("parent")));
+    /// child.on_discard(move || log.update(|l| l.push("child")));
+    ///
+    /// parent.discard();
+    /// assert_eq!(log.cloned(), vec!["child", "parent"]);
+    /// ```
+    pub fn on_discard(&self, f: impl FnOnce() + 'static) {
+        self.rt.with_ref(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            rt.scope_tree[self.sx].cleanups.add(None, Box::new(f))
+        });
+    }
+
+    /// Registers a function that is run before the running func signal runs again,
+    /// and when its scope is discarded. This makes it possible to release resources
+    /// such as timers and channels that the func signal has acquired.
+    ///
+    /// When called outside of a func signal it is the same as [on_discard()](Self::on_discard()).
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let released = signal!(sc, Vec::<i32>::new());
+    ///
+    /// signal!(sc, move || {
+    ///     let acquired = count.get();
+    ///     sc.on_cleanup(move || released.update(|r| r.push(acquired)));
+    /// });
+    ///
+    /// count.set(2);
+    /// assert_eq!(released.cloned(), vec![1]);
+    /// ```
+    pub fn on_cleanup(&self, f: impl FnOnce() + 'static) {
+        self.rt.with_ref(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            match rt.get_running_signal() {
+                Some(signal) => rt.scope_of(signal).cleanups.add(Some(signal), Box::new(f)),
+                None => rt.scope_tree[self.sx].cleanups.add(None, Box::new(f)),
+            }
+        })
+    }
+
+    /// Makes the value available to this scope and its descendants with
+    /// [use_context()](Self::use_context()). It replaces any value of the same type
+    /// that this scope provides and shadows those provided by its ancestors.
+    ///
+    /// The value is dropped when the scope is discarded.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal, types::EqData, Signal};
+    ///
+    /// #[derive(Clone, Copy)]
+    /// struct Theme(Signal<EqData<&'static str>, ClientRuntime>);
+    ///
+    /// let root = ClientRuntime::new_root_scope();
+    /// root.provide_context(Theme(signal!(root, "dark")));
+    ///
+    /// let child = root.new_child().new_child();
+    /// let theme = child.use_context::<Theme>().unwrap();
+    /// assert_eq!(theme.0.get(), "dark");
+    ///
+    /// assert!(child.use_context::<String>().is_none());
+    /// ```
+    pub fn provide_context<T: 'static>(&self, value: T) {
+        self.rt.with_ref(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            rt.scope_tree[self.sx].contexts.provide(value)
+        })
+    }
+
+    /// A clone of the value of type `T` provided by this scope or by the
+    /// nearest of its ancestors that provides one.
+    pub fn use_context<T: Clone + 'static>(&self) -> Option<T> {
+        self.rt.with_ref(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            let mut sx = Some(self.sx);
+            while let Some(id) = sx {
+                if let Some(value) = rt.scope_tree[id].contexts.get::<T>() {
+                    return Some(value);
+                }
+                sx = rt.scope_tree.nodes[id.index()].parent;
             }
+            None
         })
     }
+
+    /// Runs the closure and defers the propagation of all signal changes made in it
+    /// until it returns. A signal that depends on several of the changed signals
+    /// only runs once. Nested batches are flattened into the outermost one.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let width = signal!(sc, 2);
+    /// let height = signal!(sc, 3);
+    /// let history = signal!(sc, Vec::<i32>::new());
+    ///
+    /// signal!(sc, move || {
+    ///     let area = width.get() * height.get();
+    ///     history.update(|h| h.push(area));
+    /// });
+    ///
+    /// sc.batch(|| {
+    ///     width.set(4);
+    ///     height.set(5);
+    /// });
+    ///
+    /// assert_eq!(history.cloned(), vec![6, 20]);
+    /// ```
+    pub fn batch<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.batch_guard();
+        f()
+    }
+
+    /// Runs the closure without subscribing the running func signal to the
+    /// signals read inside it.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let step = signal!(sc, 10);
+    /// let offset = signal!(sc, 100);
+    ///
+    /// // only re-runs when count changes
+    /// let total = signal!(sc, move || count.get() + sc.untrack(|| step.get() + offset.get()));
+    ///
+    /// step.set(20);
+    /// assert_eq!(total.get(), 111);
+    ///
+    /// count.set(2);
+    /// assert_eq!(total.get(), 122);
+    /// ```
+    pub fn untrack<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = RunningSignalGuard::new(self.rt, None);
+        f()
+    }
+
+    /// Returns a guard that defers the propagation of all signal changes until it
+    /// is dropped. See [BatchGuard].
+    pub fn batch_guard(&self) -> BatchGuard<RT> {
+        BatchGuard::new(self.rt)
+    }
+}
+
+/// Sets the running signal and restores the previous one when dropped,
+/// including when the code run in the meantime panics
+pub(crate) struct RunningSignalGuard<RT: Runtime> {
+    rt: RT,
+    previous: Option<SignalId<RT>>,
+}
+
+impl<RT: Runtime> RunningSignalGuard<RT> {
+    pub(crate) fn new(rt: RT, signal: Option<SignalId<RT>>) -> Self {
+        let previous = rt.with_ref(|rt| rt.set_running_signal(signal));
+        Self { rt, previous }
+    }
+}
+
+impl<RT: Runtime> Drop for RunningSignalGuard<RT> {
+    fn drop(&mut self) {
+        self.rt
+            .with_ref(|rt| rt.set_running_signal(self.previous.take()));
+    }
 }
@@ -1,11 +1,18 @@
 use crate::{
-    arena_tree::FlagVec, runtimes::Runtime, scope::Scope, signals::SignalId, signals::SignalInner,
-    CellType,
+    arena_tree::FlagVec,
+    runtimes::Runtime,
+    scope::Scope,
+    signals::{SignalId, SignalInner, SignalValue},
+    CellType, ReactiveError,
 };
 
+use super::{cleanup::Cleanups, context::Contexts};
+
 #[derive(Debug, Default)]
 pub(crate) struct ScopeInner<RT: Runtime> {
     signals: CellType<Vec<SignalInner<RT>>>,
+    pub(crate) cleanups: Cleanups<RT>,
+    pub(crate) contexts: Contexts,
 }
 
 impl<RT: Runtime> ScopeInner<RT> {
@@ -31,17 +38,46 @@ impl<RT: Runtime> ScopeInner<RT> {
         f(signal)
     }
 
+    /// Same as `with_signal` but returns an error instead of panicking when
+    /// the signals are already mutably borrowed or the signal doesn't exist.
+    pub fn try_with_signal<F, T>(&self, id: SignalId<RT>, f: F) -> Result<T, ReactiveError>
+    where
+        F: FnOnce(&SignalInner<RT>) -> Result<T, ReactiveError>,
+    {
+        let signals = self.try_vec_ref()?;
+        let signal = signals
+            .get(id.index())
+            .ok_or(ReactiveError::DiscardedScope)?;
+        f(signal)
+    }
+
+    pub fn set_signal_value(&self, id: SignalId<RT>, value: SignalValue) {
+        #[allow(unused_mut)]
+        let mut signals = self.vec_mut();
+        signals[id.index()].value = value;
+    }
+
     pub(crate) fn remove_scopes(&mut self, discarded_scopes: &FlagVec) {
         #[allow(unused_mut)]
         let mut signals = self.vec_mut();
         signals.iter_mut().for_each(|signal| {
             signal
                 .listeners
-                .retain(|s| !discarded_scopes.get(s.sx.as_raw() as usize))
+                .retain(|s| !discarded_scopes.get(s.sx.index()));
+            signal
+                .sources
+                .retain(|s| !discarded_scopes.get(s.sx.index()));
         });
     }
 
+    /// Runs the cleanups registered by the signal during its last run
+    pub(crate) fn run_cleanups(&self, id: SignalId<RT>) {
+        self.cleanups.take_for(id).into_iter().for_each(|f| f());
+    }
+
     pub(crate) fn reuse(&self) {
+        drop(self.cleanups.take_all());
+        drop(self.contexts.take_all());
         #[allow(unused_mut)]
         let mut signals = self.vec_mut();
         signals.iter_mut().for_each(|signal| signal.reuse());
@@ -60,6 +96,13 @@ impl<RT: Runtime> ScopeInner<RT> {
     fn vec_mut(&self) -> std::cell::RefMut<Vec<SignalInner<RT>>> {
         self.signals.borrow_mut()
     }
+
+    #[inline]
+    fn try_vec_ref(&self) -> Result<std::cell::Ref<'_, Vec<SignalInner<RT>>>, ReactiveError> {
+        self.signals
+            .try_borrow()
+            .map_err(|_| ReactiveError::ReentrantBorrow)
+    }
 }
 #[cfg(feature = "unsafe-cell")]
 impl<RT: Runtime> ScopeInner<RT> {
@@ -72,4 +115,9 @@ impl<RT: Runtime> ScopeInner<RT> {
     fn vec_mut(&self) -> &mut Vec<SignalInner<RT>> {
         unsafe { &mut *self.signals.get() }
     }
+
+    #[inline]
+    fn try_vec_ref(&self) -> Result<&Vec<SignalInner<RT>>, ReactiveError> {
+        Ok(self.vec_ref())
+    }
 }
@@ -50,6 +50,57 @@ fn test_scopes_discard() {
     assert_eq!(output.values(), "val: 5, val: 4");
 }
 
+#[test]
+fn test_scopes_batch() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, 2);
+
+    let output = Rc::new(StringStore::new());
+    let _sum_sig = signal!(sc, clone: output, move || output
+        .push(format!("sum: {}", a_sig.get() + b_sig.get())));
+
+    let r = sc.batch(|| {
+        a_sig.set(10);
+        // nested batches are flattened
+        sc.batch(|| b_sig.set(20));
+        b_sig.set(30);
+        assert_eq!(output.values(), "sum: 3");
+        "done"
+    });
+
+    assert_eq!(r, "done");
+    assert_eq!(output.values(), "sum: 3, sum: 40");
+
+    // a batch without changes doesn't run anything
+    sc.batch(|| a_sig.set(10));
+    assert_eq!(output.values(), "sum: 3, sum: 40");
+}
+
+#[test]
+fn test_scopes_discard_in_batch() {
+    let root = ServerRuntime::new_root_scope();
+
+    let output = Rc::new(StringStore::new());
+    let sc = root.new_child();
+    let old_sig = signal!(sc, 1);
+    signal!(sc, clone: output, move || output.push(format!("old: {}", old_sig.get())));
+
+    root.batch(|| {
+        old_sig.set(2);
+        sc.discard();
+
+        // the signals of the new scope reuse the ids of the discarded ones
+        let sc = root.new_child();
+        let new_sig = signal!(sc, 10);
+        signal!(sc, clone: output, move || output.push(format!("new: {}", new_sig.get())));
+    });
+
+    // the change of the discarded signal isn't propagated
+    assert_eq!(output.values(), "old: 1, new: 10");
+}
+
 #[derive(Copy, Clone)]
 pub struct ScopeId<RT: Runtime> {
     pub(crate) _sx: usize,
@@ -162,3 +213,214 @@ fn chained_deref() {
     wrap2.get_some_data();
     some_func(wrap2);
 }
+
+#[test]
+fn test_scopes_on_cleanup() {
+    let root = ServerRuntime::new_root_scope();
+    let num_sig = signal!(root, 1);
+
+    let output = Rc::new(StringStore::new());
+    let sc = root.new_child();
+    let child = sc.new_child();
+
+    sc.on_discard(clone_push(&output, "discard sc"));
+    child.on_discard(clone_push(&output, "discard child"));
+
+    let out = output.clone();
+    signal!(child, move || {
+        let num = num_sig.get();
+        child.on_cleanup(clone_push(&out, &format!("cleanup {num}")));
+    });
+
+    num_sig.set(2);
+    assert_eq!(output.values(), "cleanup 1");
+
+    sc.discard();
+    assert_eq!(
+        output.values(),
+        "cleanup 1, discard child, cleanup 2, discard sc"
+    );
+
+    // the discarded func signal is no longer run
+    num_sig.set(3);
+    assert_eq!(
+        output.values(),
+        "cleanup 1, discard child, cleanup 2, discard sc"
+    );
+}
+
+#[test]
+fn test_scopes_discard_root() {
+    let root = ServerRuntime::new_root_scope();
+    let output = Rc::new(StringStore::new());
+    root.on_discard(clone_push(&output, "discard root"));
+    root.discard();
+    assert_eq!(output.values(), "discard root");
+}
+
+fn clone_push(output: &Rc<StringStore>, value: &str) -> impl FnOnce() + 'static {
+    let output = output.clone();
+    let value = value.to_string();
+    move || output.push(value)
+}
+
+#[test]
+#[should_panic(expected = "the scope has been discarded")]
+fn test_scopes_stale_signal() {
+    let root = ServerRuntime::new_root_scope();
+
+    let sc = root.new_child();
+    let stale = signal!(sc, 1);
+    sc.discard();
+
+    // the new scope reuses the slot of the discarded one
+    let sc = root.new_child();
+    let _sig = signal!(sc, 2);
+
+    stale.get();
+}
+
+#[test]
+#[should_panic(expected = "has been discarded")]
+fn test_scopes_stale_scope() {
+    let root = ServerRuntime::new_root_scope();
+
+    let stale = root.new_child();
+    stale.discard();
+    let _sc = root.new_child();
+
+    signal!(stale, 1);
+}
+
+#[test]
+fn test_scopes_stale_signal_reused_runtime() {
+    use crate::ReactiveError;
+
+    // the slot of the signal's scope is reused before the runtime is discarded
+    let root = ServerRuntime::new_root_scope();
+    root.new_child().discard();
+    let sc = root.new_child();
+    let stale = signal!(sc, 1);
+    root.discard();
+
+    let root = ServerRuntime::new_root_scope();
+    root.discard();
+
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
+    let _sig = signal!(sc, 42);
+
+    assert_eq!(stale.try_get(), Err(ReactiveError::DiscardedScope));
+}
+
+#[test]
+fn test_scopes_stale_signal_retired_slot() {
+    use crate::ReactiveError;
+
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
+    let stale = signal!(sc, 1);
+    sc.discard();
+
+    // enough reuses for the generation of the slot to wrap around
+    for _ in 0..u16::MAX {
+        root.new_child().discard();
+    }
+    let sc = root.new_child();
+    let _sig = signal!(sc, 2);
+
+    assert_eq!(stale.try_get(), Err(ReactiveError::DiscardedScope));
+}
+
+#[test]
+fn test_scopes_stale_signal_retired_runtime() {
+    use crate::ReactiveError;
+
+    let root = ServerRuntime::new_root_scope();
+    let stale = signal!(root, 1);
+    root.discard();
+
+    for _ in 0..u16::MAX {
+        ServerRuntime::new_root_scope().discard();
+    }
+    let root = ServerRuntime::new_root_scope();
+    let _sig = signal!(root, 2);
+
+    assert_eq!(stale.try_get(), Err(ReactiveError::DiscardedRuntime));
+}
+
+#[test]
+fn test_scopes_discard_root_resets_runtime() {
+    // a batch that never ends is forgotten with the runtime
+    let root = ServerRuntime::new_root_scope();
+    std::mem::forget(root.batch_guard());
+    root.discard();
+
+    let root = ServerRuntime::new_root_scope();
+    let num_sig = signal!(root, 1);
+    let double_sig = signal!(root, move || num_sig.get() * 2);
+    num_sig.set(2);
+    assert_eq!(double_sig.get(), 4);
+}
+
+#[test]
+fn test_scopes_discard_listener() {
+    let root = ServerRuntime::new_root_scope();
+    let num_sig = signal!(root, 1);
+
+    let output = Rc::new(StringStore::new());
+    let sc = root.new_child();
+    signal!(sc, clone: output, move || output.push(format!("val: {}", num_sig.get())));
+
+    sc.discard();
+    // the discarded listener is not run
+    num_sig.set(2);
+    assert_eq!(output.values(), "val: 1");
+}
+
+#[test]
+fn test_scopes_discard_keeps_other_listeners() {
+    let root = ServerRuntime::new_root_scope();
+    let num_sig = signal!(root, 1);
+
+    let output = Rc::new(StringStore::new());
+    signal!(root, clone: output, move || output.push(format!("root: {}", num_sig.get())));
+    let sc = root.new_child();
+    signal!(sc, clone: output, move || output.push(format!("sc: {}", num_sig.get())));
+
+    // only the listeners of the discarded scope are removed
+    sc.discard();
+    num_sig.set(2);
+    assert_eq!(output.values(), "root: 1, sc: 1, root: 2");
+}
+
+#[test]
+fn test_scopes_context() {
+    let root = ServerRuntime::new_root_scope();
+    root.provide_context(1u8);
+    root.provide_context("root");
+
+    let parent = root.new_child();
+    parent.provide_context(2u8);
+    let child = parent.new_child();
+    let sibling = root.new_child();
+
+    assert_eq!(child.use_context::<u8>(), Some(2));
+    assert_eq!(child.use_context::<&str>(), Some("root"));
+    assert_eq!(sibling.use_context::<u8>(), Some(1));
+    assert_eq!(root.use_context::<u16>(), None);
+
+    parent.provide_context(3u8);
+    assert_eq!(child.use_context::<u8>(), Some(3));
+
+    // the value is dropped with the scope
+    let value = Rc::new(());
+    parent.provide_context(value.clone());
+    assert_eq!(Rc::strong_count(&value), 2);
+    parent.discard();
+    assert_eq!(Rc::strong_count(&value), 1);
+
+    // a new scope in the discarded slot doesn't inherit its values
+    let reused = root.new_child();
+    assert_eq!(reused.use_context::<u8>(), Some(1));
+}
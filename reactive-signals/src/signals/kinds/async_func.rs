@@ -0,0 +1,87 @@
+use std::rc::Rc;
+
+use crate::{
+    primitives::AnyData,
+    runtimes::Runtime,
+    signals::{spawner::spawner, Data, EqData, Modifiable, SignalType},
+    Scope, Signal,
+};
+
+pub trait EqAsyncFuncKind {
+    #[inline]
+    fn async_kind(&self) -> EqAsyncFuncSignal {
+        EqAsyncFuncSignal
+    }
+}
+
+// Does not require any autoref if called as (&&tuple).async_kind().
+impl<F, T, RT: Runtime> EqAsyncFuncKind for &(Scope<RT>, F)
+where
+    F: AsyncFn() -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait TrueAsyncFuncKind {
+    #[inline]
+    fn async_kind(&self) -> TrueAsyncFunc {
+        TrueAsyncFunc
+    }
+}
+
+// Requires one deref to call! Lower priority than EqAsyncFuncKind.
+impl<F, T, RT: Runtime> TrueAsyncFuncKind for (Scope<RT>, F)
+where
+    F: AsyncFn() -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct EqAsyncFuncSignal;
+
+impl EqAsyncFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT>(self, tuple: (Scope<RT>, F)) -> Signal<EqData<Option<T>>, RT>
+    where
+        F: AsyncFn() -> T + 'static,
+        T: PartialEq + 'static,
+        RT: Runtime + Send + Sync + 'static,
+    {
+        let (sx, func) = tuple;
+        new_async(sx, func)
+    }
+}
+
+pub struct TrueAsyncFunc;
+
+impl TrueAsyncFunc {
+    #[inline]
+    pub fn new<F, T, RT>(self, tuple: (Scope<RT>, F)) -> Signal<Data<Option<T>>, RT>
+    where
+        F: AsyncFn() -> T + 'static,
+        T: 'static,
+        RT: Runtime + Send + Sync + 'static,
+    {
+        let (sx, func) = tuple;
+        new_async(sx, func)
+    }
+}
+
+fn new_async<F, T, W, RT>(sx: Scope<RT>, func: F) -> Signal<W, RT>
+where
+    F: AsyncFn() -> T + 'static,
+    T: 'static,
+    W: SignalType<Inner = Option<T>> + Modifiable,
+    RT: Runtime + Send + Sync + 'static,
+{
+    let value = Signal::<W, RT>::data(sx, AnyData::new(W::new(None)));
+    let func = Rc::new(func);
+    spawner(sx, move || {
+        let func = func.clone();
+        async move {
+            let result = func().await;
+            value.set(Some(result));
+        }
+    });
+    value
+}
@@ -10,8 +10,8 @@ pub trait ClientEqFuncKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<F, T, RT: Runtime> ClientEqFuncKind for (Scope<RT>, F)
+// Does not require any autoref if called as (&&tuple).signal_kind().
+impl<F, T, RT: Runtime> ClientEqFuncKind for &(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: PartialEq + 'static,
@@ -25,8 +25,8 @@ pub trait ClientTrueFuncKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<F, T, RT: Runtime> ClientTrueFuncKind for &(Scope<RT>, F)
+// Requires one deref to call! Lower priority than EqKind.
+impl<F, T, RT: Runtime> ClientTrueFuncKind for (Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: 'static,
@@ -14,7 +14,7 @@ pub trait HashEqDataKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
+// Requires three derefs to call! Lowest priority.
 impl<T, RT: Runtime> HashEqDataKind for (Scope<RT>, T) where T: Hash + PartialEq + 'static {}
 
 pub trait EqDataKind {
@@ -24,8 +24,8 @@ pub trait EqDataKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<T, RT: Runtime> EqDataKind for &(Scope<RT>, T) where T: PartialEq + 'static {}
+// Does not require any autoref if called as (&&&tuple).signal_kind().
+impl<T, RT: Runtime> EqDataKind for &&(Scope<RT>, T) where T: PartialEq + 'static {}
 
 pub trait TrueDataKind {
     #[inline]
@@ -34,8 +34,9 @@ pub trait TrueDataKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<T, RT: Runtime> TrueDataKind for &&(Scope<RT>, T) where T: 'static {}
+// Requires two derefs to call! Lower priority than the func kinds, which
+// would otherwise be taken for data.
+impl<T, RT: Runtime> TrueDataKind for &(Scope<RT>, T) where T: 'static {}
 
 pub struct HashEqSignal;
 
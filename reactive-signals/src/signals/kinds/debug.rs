@@ -0,0 +1,31 @@
+use std::fmt::Debug;
+
+use crate::{runtimes::Runtime, signals::SignalType, Signal};
+
+/// Registers how to render the value of a signal created with the
+/// [signal!](crate::signal!) macro in the snapshots, when it implements [Debug].
+pub struct DebugProbe<'a, T: SignalType, RT: Runtime>(pub &'a Signal<T, RT>);
+
+pub trait DebugValueKind {
+    fn register_debug(&self);
+}
+
+// Does not require any autoref if called as (&&probe).register_debug().
+impl<T, RT: Runtime> DebugValueKind for &DebugProbe<'_, T, RT>
+where
+    T: SignalType,
+    T::Inner: Debug,
+{
+    #[inline]
+    fn register_debug(&self) {
+        self.0.id.rt_ref(|rt| rt.value_types.register_debug::<T>())
+    }
+}
+
+pub trait NoDebugValueKind {
+    #[inline]
+    fn register_debug(&self) {}
+}
+
+// Requires one deref to call! Lower priority than DebugValueKind.
+impl<T: SignalType, RT: Runtime> NoDebugValueKind for DebugProbe<'_, T, RT> {}
@@ -12,8 +12,8 @@ pub trait EqFuncKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<F, T, RT: Runtime> EqFuncKind for (Scope<RT>, F)
+// Does not require any autoref if called as (&&&tuple).signal_kind().
+impl<F, T, RT: Runtime> EqFuncKind for &&(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: PartialEq + 'static,
@@ -27,8 +27,8 @@ pub trait TrueFuncKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<F, T, RT: Runtime> TrueFuncKind for &(Scope<RT>, F)
+// Requires one autoref to call! Lower priority than EqKind.
+impl<F, T, RT: Runtime> TrueFuncKind for &&&(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: 'static,
@@ -0,0 +1,61 @@
+use crate::primitives::DynFunc;
+use crate::{runtimes::Runtime, Scope};
+
+use crate::signals::{LazyEqFunc, LazyFunc, Signal};
+
+pub trait LazyEqFuncKind {
+    #[inline]
+    fn lazy_kind(&self) -> LazyEqFuncSignal {
+        LazyEqFuncSignal
+    }
+}
+
+// Does not require any autoref if called as (&&tuple).lazy_kind().
+impl<F, T, RT: Runtime> LazyEqFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait LazyTrueFuncKind {
+    #[inline]
+    fn lazy_kind(&self) -> LazyTrueFunc {
+        LazyTrueFunc
+    }
+}
+
+// Requires one deref to call! Lower priority than EqKind.
+impl<F, T, RT: Runtime> LazyTrueFuncKind for (Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct LazyEqFuncSignal;
+
+impl LazyEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<LazyEqFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: PartialEq + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new_lazy::<F, T, LazyEqFunc<T>>(func))
+    }
+}
+pub struct LazyTrueFunc;
+
+impl LazyTrueFunc {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<LazyFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new_lazy::<F, T, LazyFunc<T>>(func))
+    }
+}
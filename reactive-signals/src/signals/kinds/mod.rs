@@ -1,9 +1,15 @@
 #![doc(hidden)]
 
+mod async_func;
 mod client;
 mod data;
+#[cfg(feature = "snapshots")]
+mod debug;
 mod func;
+mod lazy;
+mod prev;
 mod server;
+mod timer;
 
 // https://github.com/dtolnay/case-studies/tree/master/autoref-specialization
 
@@ -14,3 +20,14 @@ pub use data::{EqDataKind, HashEqDataKind, TrueDataKind};
 pub use server::{ServerEqFuncKind, ServerTrueFuncKind};
 
 pub use client::{ClientEqFuncKind, ClientTrueFuncKind};
+
+pub use lazy::{LazyEqFuncKind, LazyTrueFuncKind};
+
+pub use prev::{EqPrevFuncKind, TruePrevFuncKind};
+
+pub use async_func::{EqAsyncFuncKind, TrueAsyncFuncKind};
+
+pub use timer::{EqTimerKind, TrueTimerKind};
+
+#[cfg(feature = "snapshots")]
+pub use debug::{DebugProbe, DebugValueKind, NoDebugValueKind};
@@ -0,0 +1,66 @@
+use crate::{
+    primitives::DynFunc,
+    runtimes::Runtime,
+    signals::{EqFunc, Func},
+    Scope, Signal,
+};
+
+// The value type is a parameter of the kind traits because it only appears in
+// the argument of the function, where it doesn't constrain the impls.
+
+pub trait EqPrevFuncKind<T> {
+    #[inline]
+    fn signal_kind(&self) -> EqPrevFuncSignal {
+        EqPrevFuncSignal
+    }
+}
+
+// Does not require any autoref if called as (&&&tuple).signal_kind().
+impl<F, T, RT: Runtime> EqPrevFuncKind<T> for &&(Scope<RT>, F)
+where
+    F: Fn(Option<&T>) -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait TruePrevFuncKind<T> {
+    #[inline]
+    fn signal_kind(&self) -> TruePrevFunc {
+        TruePrevFunc
+    }
+}
+
+// Requires one autoref to call! Lower priority than EqPrevFuncKind.
+impl<F, T, RT: Runtime> TruePrevFuncKind<T> for &&&(Scope<RT>, F)
+where
+    F: Fn(Option<&T>) -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct EqPrevFuncSignal;
+
+impl EqPrevFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<EqFunc<T>, RT>
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: PartialEq + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new_prev::<F, T, EqFunc<T>>(func))
+    }
+}
+pub struct TruePrevFunc;
+
+impl TruePrevFunc {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<Func<T>, RT>
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new_prev::<F, T, Func<T>>(func))
+    }
+}
@@ -0,0 +1,107 @@
+use std::{rc::Rc, time::Duration};
+
+use crate::{
+    primitives::AnyData,
+    runtimes::{timers::Sleep, Runtime},
+    signals::{spawner::spawner, Data, EqData, Modifiable, SignalType},
+    Scope, Signal,
+};
+
+pub trait EqTimerKind {
+    #[inline]
+    fn timer_kind(&self) -> EqTimerSignal {
+        EqTimerSignal
+    }
+}
+
+// Does not require any autoref if called as (&&tuple).timer_kind().
+impl<F, T, RT: Runtime> EqTimerKind for &(Scope<RT>, F)
+where
+    F: AsyncFn(&mut Option<Duration>) -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait TrueTimerKind {
+    #[inline]
+    fn timer_kind(&self) -> TrueTimer {
+        TrueTimer
+    }
+}
+
+// Requires one deref to call! Lower priority than EqTimerKind.
+impl<F, T, RT: Runtime> TrueTimerKind for (Scope<RT>, F)
+where
+    F: AsyncFn(&mut Option<Duration>) -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct EqTimerSignal;
+
+impl EqTimerSignal {
+    #[inline]
+    pub fn new<F, T, RT>(
+        self,
+        tuple: (Scope<RT>, F),
+        interval: Duration,
+    ) -> Signal<EqData<Option<T>>, RT>
+    where
+        F: AsyncFn(&mut Option<Duration>) -> T + 'static,
+        T: PartialEq + 'static,
+        RT: Runtime + Send + Sync + 'static,
+    {
+        let (sx, func) = tuple;
+        new_timer(sx, func, interval)
+    }
+}
+
+pub struct TrueTimer;
+
+impl TrueTimer {
+    #[inline]
+    pub fn new<F, T, RT>(
+        self,
+        tuple: (Scope<RT>, F),
+        interval: Duration,
+    ) -> Signal<Data<Option<T>>, RT>
+    where
+        F: AsyncFn(&mut Option<Duration>) -> T + 'static,
+        T: 'static,
+        RT: Runtime + Send + Sync + 'static,
+    {
+        let (sx, func) = tuple;
+        new_timer(sx, func, interval)
+    }
+}
+
+/// The shortest interval between two runs, so that a timer with an interval of zero
+/// doesn't keep running without letting the clock move.
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Timers only run on the client, on the server the value stays `None`.
+fn new_timer<F, T, W, RT>(sx: Scope<RT>, func: F, interval: Duration) -> Signal<W, RT>
+where
+    F: AsyncFn(&mut Option<Duration>) -> T + 'static,
+    T: 'static,
+    W: SignalType<Inner = Option<T>> + Modifiable,
+    RT: Runtime + Send + Sync + 'static,
+{
+    let value = Signal::<W, RT>::data(sx, AnyData::new(W::new(None)));
+    if RT::IS_SERVER {
+        return value;
+    }
+    let func = Rc::new(func);
+    spawner(sx, move || {
+        let func = func.clone();
+        async move {
+            let mut interval = Some(interval);
+            while let Some(duration) = interval {
+                Sleep::new(sx.rt, duration.max(MIN_INTERVAL)).await;
+                let result = func(&mut interval).await;
+                value.set(Some(result));
+            }
+        }
+    });
+    value
+}
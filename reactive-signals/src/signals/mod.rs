@@ -3,18 +3,23 @@ mod tests;
 
 pub(crate) mod kinds;
 mod signal_accessors;
+mod signal_combinators;
 mod signal_id;
 mod signal_inner;
 mod signal_new;
+mod signal_polled;
+pub(crate) mod spawner;
 pub mod types;
 mod updater;
 
 use std::marker::PhantomData;
 
 use crate::runtimes::Runtime;
-pub(crate) use signal_id::SignalId;
+pub use signal_id::SignalId;
+pub(crate) use signal_id::SignalIndex;
 pub(crate) use signal_inner::{SignalInner, SignalValue};
 pub(crate) use types::*;
+pub(crate) use updater::{propagate_change, propagate_changes};
 
 #[doc(hidden)]
 pub use kinds::*;
@@ -42,6 +47,24 @@ pub use kinds::*;
 /// | [Clone]          | .cloned              | .cloned     | .opt_cloned                              |
 /// | [Copy]           | .get                 | .get        | .opt_get                                 |
 ///
+/// Reading a signal from inside a func signal subscribes the func signal to it. Use
+/// `.untracked_get`, `.untracked_cloned` or `.with_untracked` to read a value without
+/// subscribing, or [Scope::untrack](crate::Scope::untrack) for a whole closure.
+///
+/// The `.try_get`, `.try_cloned`, `.try_with`, `.try_set` and `.try_update` accessors return a
+/// [ReactiveError](crate::ReactiveError) instead of panicking, for instance when the signal's
+/// scope has been discarded.
+///
+/// Lazy func signals, created with `signal!(sx, lazy, ...)`, are read with the same accessors
+/// but only compute their value when read.
+///
+/// ## Combinators
+///
+/// Derived signals can be created without access to the [Scope](crate::Scope) by passing
+/// the result of `.map`, `.select` or `.zip` to the `signal!` macro, as in
+/// `signal!(count.map(|c| c * 2))`. They are created in the scope of the signal the
+/// combinator is called on.
+///
 ///
 /// ## Example
 ///
@@ -85,13 +108,9 @@ pub use kinds::*;
 /// name.update(|t| *t = "fig");
 /// assert_eq!(text.cloned(), "1 fig");
 ///
-/// // 1 kiwi is repated because when changing count, is_plural changes as well
-/// // triggering a second update of the text. This will be detected in
-/// // future versions and only notified once.
-/// assert_eq!(
-///     history.with(|h| h.join(", ")),
-///     "5 kiwis, 1 kiwi, 1 kiwi, 1 fig"
-/// );
+/// // when changing count, is_plural changes as well but the text
+/// // is only updated once, after both count and is_plural are updated.
+/// assert_eq!(history.with(|h| h.join(", ")), "5 kiwis, 1 kiwi, 1 fig");
 ///
 /// with_signal_arg(count);
 ///
@@ -104,6 +123,8 @@ pub use kinds::*;
 /// ```
 pub struct Signal<T: SignalType, RT: Runtime> {
     id: SignalId<RT>,
+    /// The generation of the signal's scope
+    generation: u16,
     ty: PhantomData<T>,
 }
 
@@ -111,6 +132,7 @@ impl<T: SignalType, RT: Runtime> Clone for Signal<T, RT> {
     fn clone(&self) -> Self {
         Self {
             id: self.id,
+            generation: self.generation,
             ty: self.ty,
         }
     }
@@ -159,11 +181,7 @@ fn test_example() {
     name.update(|t| *t = "fig");
     assert_eq!(text.cloned(), "1 fig");
 
-    // 1 kiwi is repated because when changing count, is_plural changes as well
-    // triggering a second update of the text. This will be detected in
-    // future versions and only notified once.
-    assert_eq!(
-        history.with(|h| h.join(", ")),
-        "5 kiwis, 1 kiwi, 1 kiwi, 1 fig"
-    );
+    // when changing count, is_plural changes as well but the text
+    // is only updated once, after both count and is_plural are updated.
+    assert_eq!(history.with(|h| h.join(", ")), "5 kiwis, 1 kiwi, 1 fig");
 }
@@ -1,7 +1,10 @@
-use crate::runtimes::Runtime;
+use crate::{
+    error::ReactiveError,
+    runtimes::{Runtime, RuntimeInner},
+};
 
 use super::{
-    updater::propagate_change, Modifiable, OptReadable, Readable, Signal, SignalId, SignalInner,
+    updater::update_if_pending, Modifiable, OptReadable, Readable, Signal, SignalId, SignalInner,
     SignalType,
 };
 
@@ -13,21 +16,50 @@ where
     /// Set the signal's value and notifies subscribers
     /// if the value changed when it implements `PartialEq`
     /// otherwise it always notifies.
+    ///
+    /// In debug builds it panics if a [cycle](crate::CycleError) is detected.
     pub fn set(&self, val: T::Inner) {
-        self.id.rt_ref(|rt| {
-            let is_equal = rt[self.id].with_signal(self.id, |sig| sig.value().set::<T>(val));
-            if !is_equal {
-                propagate_change(rt, self.id);
-            }
+        self.rt_ref(|rt| {
+            self.set_and_propagate(rt, val);
+            rt.check_cycle();
         });
     }
 
+    /// Same as [set](Self::set) but returns an error instead of panicking when the
+    /// signal cannot be set: its scope or runtime has been discarded, its runtime
+    /// is on another thread or its value is borrowed. The value is then unchanged.
+    ///
+    /// Only this signal is checked. Once its value is set the change is propagated
+    /// like with [set](Self::set), and the func signals it re-runs can still panic,
+    /// for instance when they read a signal of a discarded scope. A [cycle](crate::CycleError)
+    /// detected during the propagation is returned as [ReactiveError::Cycle], in which
+    /// case the value has been set.
+    pub fn try_set(&self, val: T::Inner) -> Result<(), ReactiveError> {
+        self.try_rt_ref(|rt| {
+            rt.scope_of(self.id)
+                .try_with_signal(self.id, |sig| sig.value().check_borrow(true))?;
+            self.set_and_propagate(rt, val);
+            Ok(rt.take_cycle_error()?)
+        })
+    }
+
+    fn set_and_propagate(&self, rt: &RuntimeInner<RT>, val: T::Inner) {
+        let is_equal = rt
+            .scope_of(self.id)
+            .with_signal(self.id, |sig| sig.value().set::<T>(val));
+        if !is_equal {
+            rt.signal_changed(self.id);
+        }
+    }
+
     /// Applies a function to the current value to mutate it in place and returns
     /// whatever that function returns.
     ///
     /// Subscribers are notified if the value changed when it implements [PartialEq]
     /// otherwise it always notifies.
     ///
+    /// In debug builds it panics if a [cycle](crate::CycleError) is detected.
+    ///
     /// **Example of using the return value**
     ///
     /// ```rust
@@ -41,15 +73,45 @@ where
     /// ```
     ///
     pub fn update<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> R {
-        self.id.rt_ref(|rt| {
-            let (is_equal, r) =
-                rt[self.id].with_signal(self.id, |sig| sig.value().update::<T, R>(f));
-            if !is_equal {
-                propagate_change(rt, self.id);
-            }
+        self.rt_ref(|rt| {
+            let r = self.update_and_propagate(rt, f);
+            rt.check_cycle();
             r
         })
     }
+
+    /// Same as [update](Self::update) but returns an error instead of panicking when the
+    /// signal cannot be updated, with the same guarantees as [try_set](Self::try_set).
+    ///
+    /// When a [cycle](crate::CycleError) is detected during the propagation, the function
+    /// has been applied but what it returned is dropped, as [ReactiveError::Cycle] is
+    /// returned instead.
+    pub fn try_update<R: 'static>(
+        &self,
+        f: impl Fn(&mut T::Inner) -> R,
+    ) -> Result<R, ReactiveError> {
+        self.try_rt_ref(|rt| {
+            rt.scope_of(self.id)
+                .try_with_signal(self.id, |sig| sig.value().check_borrow(true))?;
+            let r = self.update_and_propagate(rt, f);
+            rt.take_cycle_error()?;
+            Ok(r)
+        })
+    }
+
+    fn update_and_propagate<R: 'static>(
+        &self,
+        rt: &RuntimeInner<RT>,
+        f: impl Fn(&mut T::Inner) -> R,
+    ) -> R {
+        let (is_equal, r) = rt
+            .scope_of(self.id)
+            .with_signal(self.id, |sig| sig.value().update::<T, R>(f));
+        if !is_equal {
+            rt.signal_changed(self.id);
+        }
+        r
+    }
 }
 
 impl<T, RT> Signal<T, RT>
@@ -60,7 +122,13 @@ where
 {
     /// Get a copy of the signal value (if the value implements [Copy])
     pub fn get(&self) -> T::Inner {
-        register_and_run(self.id, |sig| sig.value().get::<T>())
+        register_and_run(self, |sig| sig.value().get::<T>())
+    }
+
+    /// Same as [get](Self::get) but returns an error instead of
+    /// panicking when the signal cannot be used.
+    pub fn try_get(&self) -> Result<T::Inner, ReactiveError> {
+        try_register_and_run(self, |sig| sig.value().get::<T>())
     }
 }
 
@@ -74,7 +142,13 @@ where
     ///
     /// Use the `.with()` function if you can in order to avoid the clone.
     pub fn cloned(&self) -> T::Inner {
-        register_and_run(self.id, |sig| sig.value().cloned::<T>())
+        register_and_run(self, |sig| sig.value().cloned::<T>())
+    }
+
+    /// Same as [cloned](Self::cloned) but returns an error instead of
+    /// panicking when the signal cannot be used.
+    pub fn try_cloned(&self) -> Result<T::Inner, ReactiveError> {
+        try_register_and_run(self, |sig| sig.value().cloned::<T>())
     }
 }
 
@@ -99,7 +173,62 @@ where
     /// ```
     ///
     pub fn with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
-        register_and_run(self.id, |sig| sig.value().with::<T, R>(f))
+        register_and_run(self, |sig| sig.value().with::<T, R>(f))
+    }
+
+    /// Same as [with](Self::with) but returns an error instead of
+    /// panicking when the signal cannot be used.
+    pub fn try_with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> Result<R, ReactiveError> {
+        try_register_and_run(self, |sig| sig.value().with::<T, R>(f))
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    T::Inner: Copy,
+    RT: Runtime,
+{
+    /// Get a copy of the signal value (if the value implements [Copy]) without
+    /// subscribing the running func signal to it.
+    pub fn untracked_get(&self) -> T::Inner {
+        read_untracked(self, |sig| sig.value().get::<T>())
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    T::Inner: Clone,
+    RT: Runtime,
+{
+    /// Get a clone of the signal value (if the value implements [Clone]) without
+    /// subscribing the running func signal to it.
+    pub fn untracked_cloned(&self) -> T::Inner {
+        read_untracked(self, |sig| sig.value().cloned::<T>())
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime,
+{
+    /// Same as `.with()` but without subscribing the running func signal
+    /// to this signal, i.e. a change of this signal will not re-run it.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 2);
+    /// let unit = signal!(sc, "apples");
+    /// // only re-runs when count changes
+    /// let text = signal!(sc, move || {
+    ///     unit.with_untracked(|unit| format!("{} {unit}", count.get()))
+    /// });
+    /// ```
+    pub fn with_untracked<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
+        read_untracked(self, |sig| sig.value().with::<T, R>(f))
     }
 }
 
@@ -119,7 +248,7 @@ where
 {
     /// Get a copy of the signal value (if the value implements [Copy])
     pub fn opt_get(&self) -> Option<T::Inner> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().get::<T>()))
+        Self::SHOULD_RUN.then(|| register_and_run(self, |sig| sig.value().get::<T>()))
     }
 }
 
@@ -133,7 +262,7 @@ where
     ///
     /// Use the `.with()` function if you can in order to avoid the clone.
     pub fn opt_cloned(&self) -> Option<T::Inner> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().cloned::<T>()))
+        Self::SHOULD_RUN.then(|| register_and_run(self, |sig| sig.value().cloned::<T>()))
     }
 }
 
@@ -158,23 +287,119 @@ where
     /// ```
     ///
     pub fn opt_with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> Option<R> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().with::<T, R>(f)))
+        Self::SHOULD_RUN.then(|| register_and_run(self, |sig| sig.value().with::<T, R>(f)))
+    }
+}
+
+impl<T: SignalType, RT: Runtime> Signal<T, RT> {
+    /// Gives access to the runtime after checking that the
+    /// signal's scope has not been discarded.
+    #[inline]
+    pub(super) fn rt_ref<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&RuntimeInner<RT>) -> R,
+    {
+        self.id.rt_ref(|rt| {
+            rt.check_signal(self.id, self.generation);
+            f(rt)
+        })
+    }
+
+    /// Whether func signals are subscribed to changes of the signal
+    pub(crate) fn has_listeners(&self) -> bool {
+        self.rt_ref(|rt| {
+            rt.scope_of(self.id)
+                .with_signal(self.id, |sig| !sig.listeners.is_empty())
+        })
+    }
+
+    /// Same as `rt_ref` but returns an error instead of panicking
+    #[inline]
+    fn try_rt_ref<F, R>(&self, f: F) -> Result<R, ReactiveError>
+    where
+        F: FnOnce(&RuntimeInner<RT>) -> Result<R, ReactiveError>,
+    {
+        self.id.rt.try_with_ref(|rt| {
+            rt.try_check_scope(self.id.sx, self.generation)?;
+            f(rt)
+        })
     }
 }
 
 #[inline]
-fn register_and_run<RT: Runtime, T: 'static, F: FnOnce(&SignalInner<RT>) -> T>(
-    id: SignalId<RT>,
-    f: F,
-) -> T {
-    id.rt_ref(|rt| {
-        if let Some(listener) = rt.get_running_signal() {
-            rt[id].with_signal(id, |signal| {
-                signal.listeners.insert(listener);
-                f(signal)
-            })
-        } else {
-            rt[id].with_signal(id, |signal| f(signal))
-        }
+fn register_and_run<S, RT, T, F>(sig: &Signal<S, RT>, f: F) -> T
+where
+    S: SignalType,
+    RT: Runtime,
+    T: 'static,
+    F: FnOnce(&SignalInner<RT>) -> T,
+{
+    sig.rt_ref(|rt| register_and_run_in::<S, RT, T, F>(rt, sig.id, f))
+}
+
+#[inline]
+fn try_register_and_run<S, RT, T, F>(sig: &Signal<S, RT>, f: F) -> Result<T, ReactiveError>
+where
+    S: SignalType,
+    RT: Runtime,
+    T: 'static,
+    F: FnOnce(&SignalInner<RT>) -> T,
+{
+    let id = sig.id;
+    sig.try_rt_ref(|rt| {
+        rt.scope_of(id)
+            .try_with_signal(id, |signal| signal.value().check_borrow(false))?;
+        Ok(register_and_run_in::<S, RT, T, F>(rt, id, f))
     })
 }
+
+#[inline]
+fn register_and_run_in<S, RT, T, F>(rt: &RuntimeInner<RT>, id: SignalId<RT>, f: F) -> T
+where
+    S: SignalType,
+    RT: Runtime,
+    T: 'static,
+    F: FnOnce(&SignalInner<RT>) -> T,
+{
+    update_before_read::<S, RT>(rt, id);
+    if let Some(listener) = rt.get_running_signal() {
+        rt.scope_of(listener)
+            .with_signal(listener, |running| running.sources.insert(id));
+        rt.scope_of(id).with_signal(id, |signal| {
+            signal.listeners.insert(listener);
+            f(signal)
+        })
+    } else {
+        rt.scope_of(id).with_signal(id, |signal| f(signal))
+    }
+}
+
+#[inline]
+fn read_untracked<S, RT, T, F>(sig: &Signal<S, RT>, f: F) -> T
+where
+    S: SignalType,
+    RT: Runtime,
+    T: 'static,
+    F: FnOnce(&SignalInner<RT>) -> T,
+{
+    let id = sig.id;
+    sig.rt_ref(|rt| {
+        update_before_read::<S, RT>(rt, id);
+        rt.scope_of(id).with_signal(id, f)
+    })
+}
+
+/// Signals that a propagation hasn't reached yet are brought up to date, and
+/// lazy signals are computed, when they are read
+#[inline]
+fn update_before_read<S: SignalType, RT: Runtime>(rt: &RuntimeInner<RT>, id: SignalId<RT>) {
+    update_if_pending(rt, id);
+    if S::LAZY {
+        rt.scope_of(id).with_signal(id, |signal| {
+            if signal.value().is_stale::<S>() {
+                signal.compute(rt, id);
+            }
+        });
+        rt.check_cycle();
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{runtimes::Runtime, scope::Scope, Signal};
+
+use super::{Readable, SignalType};
+
+impl<T: SignalType, RT: Runtime> Signal<T, RT> {
+    /// The [Scope] the signal was created in.
+    pub fn scope(&self) -> Scope<RT> {
+        Scope {
+            sx: self.id.sx,
+            rt: self.id.rt,
+            generation: self.generation,
+        }
+    }
+}
+
+/// The combinators give the scope and the function of a func signal deriving its value
+/// from the signal they are called on, for the [signal!](crate::signal!) macro to create it.
+/// Like for a closure, the macro detects whether the value implements [PartialEq], in which
+/// case the func signal only notifies its subscribers when its value changes.
+///
+/// The func signal is created in the scope of the signal the combinator is called on, which
+/// means that it is discarded together with it.
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime + 'static,
+{
+    /// A func signal with the value returned by the function, which is
+    /// re-run when this signal changes.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// struct Label(String);
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 3);
+    /// let is_even = signal!(count.map(|c| c % 2 == 0));
+    /// // a value that doesn't implement PartialEq notifies on each change
+    /// let label = signal!(count.map(|c| Label(format!("{c} items"))));
+    ///
+    /// count.set(4);
+    /// assert!(is_even.get());
+    /// assert_eq!(label.with(|l| l.0.clone()), "4 items");
+    /// ```
+    #[must_use = "the func signal is created by passing this to signal!"]
+    pub fn map<U, F>(&self, f: F) -> (Scope<RT>, impl Fn() -> U + 'static)
+    where
+        F: Fn(&T::Inner) -> U + 'static,
+        U: 'static,
+    {
+        let sig = *self;
+        (self.scope(), move || sig.with(&f))
+    }
+
+    /// A func signal with a clone of the part of the value that the function
+    /// selects. Subscribers are only notified when that part changes, if it
+    /// implements [PartialEq].
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// #[derive(PartialEq)]
+    /// struct User {
+    ///     name: String,
+    ///     visits: u32,
+    /// }
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let user = signal!(sc, User { name: "kiwi".to_string(), visits: 0 });
+    /// let name = signal!(user.select(|u| &u.name));
+    ///
+    /// let greetings = signal!(sc, Vec::<String>::new());
+    /// signal!(sc, move || {
+    ///     let greeting = format!("hi {}", name.cloned());
+    ///     greetings.update(|g| g.push(greeting.clone()));
+    /// });
+    ///
+    /// user.update(|u| u.visits += 1);
+    /// assert_eq!(greetings.cloned(), vec!["hi kiwi"]);
+    /// ```
+    #[must_use = "the func signal is created by passing this to signal!"]
+    pub fn select<U, F>(&self, f: F) -> (Scope<RT>, impl Fn() -> U + 'static)
+    where
+        F: Fn(&T::Inner) -> &U + 'static,
+        U: Clone + 'static,
+    {
+        self.map(move |val| f(val).clone())
+    }
+
+    /// A func signal with a tuple of clones of the values of this and the
+    /// other signal. It is created in this signal's scope so the other signal must
+    /// not be discarded before it.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::ClientRuntime, signal};
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 2);
+    /// let name = signal!(sc, "fig");
+    /// let both = signal!(count.zip(name));
+    ///
+    /// name.set("kiwi");
+    /// assert_eq!(both.get(), (2, "kiwi"));
+    /// ```
+    #[must_use = "the func signal is created by passing this to signal!"]
+    #[allow(clippy::type_complexity)]
+    pub fn zip<U>(
+        &self,
+        other: Signal<U, RT>,
+    ) -> (Scope<RT>, impl Fn() -> (T::Inner, U::Inner) + 'static)
+    where
+        T::Inner: Clone,
+        U: SignalType + Readable,
+        U::Inner: Clone,
+    {
+        let sig = *self;
+        let zipped = move || (sig.with(|v| v.clone()), other.with(|v| v.clone()));
+        (self.scope(), zipped)
+    }
+}
@@ -2,7 +2,14 @@ use std::cmp::Ordering;
 
 use crate::arena_tree::NodeId;
 
-use crate::{primitives::u15Bool, runtimes::RuntimeInner, scope::Scope, Runtime};
+use crate::{runtimes::RuntimeInner, scope::Scope, Runtime};
+
+/// The index of a signal in its scope, with a flag. Limited to 32k signals
+/// per scope by default and to 2 billion with the `large-ids` feature.
+#[cfg(not(feature = "large-ids"))]
+pub(crate) use crate::primitives::u15Bool as SignalIndex;
+#[cfg(feature = "large-ids")]
+pub(crate) use crate::primitives::u31Bool as SignalIndex;
 
 /// The SignalId has three components:
 ///
@@ -18,24 +25,27 @@ use crate::{primitives::u15Bool, runtimes::RuntimeInner, scope::Scope, Runtime};
 /// across runtimes.
 ///
 /// They are ordered by Scope and then by `id`.
+///
+/// It identifies a signal, see [Signal::id()](crate::Signal::id()). The id of a
+/// signal of a discarded scope may be reused by a new signal.
 #[derive(Clone, Copy)]
-pub(crate) struct SignalId<RT: Runtime> {
-    pub(crate) id: u15Bool,
+pub struct SignalId<RT: Runtime> {
+    pub(crate) id: SignalIndex,
     pub(crate) sx: NodeId,
     pub(crate) rt: RT,
 }
 
 impl<RT: Runtime> SignalId<RT> {
     pub(crate) fn new(id: usize, sx: Scope<RT>) -> Self {
-        if id > u15Bool::MAX as usize {
+        if id > SignalIndex::MAX as usize {
             panic!(
                 "There cannot be more than {} Signals attached to a Scope",
-                u15Bool::MAX
+                SignalIndex::MAX
             );
         }
         Self {
             sx: sx.sx,
-            id: u15Bool::new(id, false),
+            id: SignalIndex::new(id, false),
             rt: sx.rt,
         }
     }
@@ -44,6 +54,18 @@ impl<RT: Runtime> SignalId<RT> {
         self.id.as_usize()
     }
 
+    /// The flag is transparent for equality and ordering and is
+    /// used by the updater to keep track of the signals that need to re-run.
+    #[inline]
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.id.bool()
+    }
+
+    #[inline]
+    pub(crate) fn set_dirty(&mut self, dirty: bool) {
+        self.id.set_bool(dirty)
+    }
+
     #[inline]
     pub(crate) fn rt_ref<F, T>(&self, f: F) -> T
     where
@@ -87,7 +109,7 @@ impl<RT: Runtime> Ord for SignalId<RT> {
 
 impl<RT: Runtime> std::fmt::Debug for SignalId<RT> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}{}ˢⁱᵍ", self.sx, self.id.as_u15())
+        write!(f, "{:?}{}ˢⁱᵍ", self.sx, self.id.as_usize())
     }
 }
 
@@ -95,8 +117,16 @@ impl<RT: Runtime> std::fmt::Debug for SignalId<RT> {
 fn signal_id_size() {
     use crate::runtimes::{ClientRuntime, ServerRuntime};
 
-    assert_eq!(std::mem::size_of::<SignalId<ServerRuntime>>(), 8);
-    assert_eq!(std::mem::size_of::<SignalId<ClientRuntime>>(), 4);
+    #[cfg(not(feature = "large-ids"))]
+    {
+        assert_eq!(std::mem::size_of::<SignalId<ServerRuntime>>(), 8);
+        assert_eq!(std::mem::size_of::<SignalId<ClientRuntime>>(), 4);
+    }
+    #[cfg(feature = "large-ids")]
+    {
+        assert_eq!(std::mem::size_of::<SignalId<ServerRuntime>>(), 12);
+        assert_eq!(std::mem::size_of::<SignalId<ClientRuntime>>(), 8);
+    }
 }
 
 #[test]
@@ -104,25 +134,25 @@ fn signal_id_ordering() {
     use crate::runtimes::ServerRuntime;
 
     let sig1_scope1 = SignalId {
-        id: u15Bool::new(1, false),
+        id: SignalIndex::new(1, false),
         sx: NodeId::from(1),
         rt: ServerRuntime::from(4),
     };
 
     let sig2_scope1 = SignalId {
-        id: u15Bool::new(2, false),
+        id: SignalIndex::new(2, false),
         sx: NodeId::from(1),
         rt: ServerRuntime::from(4),
     };
 
     let sig1_scope2 = SignalId {
-        id: u15Bool::new(1, false),
+        id: SignalIndex::new(1, false),
         sx: NodeId::from(2),
         rt: ServerRuntime::from(4),
     };
 
     let sig2_scope2 = SignalId {
-        id: u15Bool::new(2, false),
+        id: SignalIndex::new(2, false),
         sx: NodeId::from(2),
         rt: ServerRuntime::from(4),
     };
@@ -16,8 +16,11 @@ pub enum SignalValue {
 
 #[derive(Debug)]
 pub(crate) struct SignalInner<RT: Runtime> {
-    pub(super) value: SignalValue,
+    pub(crate) value: SignalValue,
+    /// The signals that are subscribed to this signal
     pub(crate) listeners: SignalSet<3, SignalId<RT>>,
+    /// The signals that this signal subscribed to during its last run
+    pub(crate) sources: SignalSet<3, SignalId<RT>>,
 }
 
 impl<RT: Runtime> SignalInner<RT> {
@@ -29,21 +32,51 @@ impl<RT: Runtime> SignalInner<RT> {
         }
     }
 
+    /// Runs the signal's function and returns true if the value changed.
+    /// A lazy signal that nobody listens to is invalidated instead.
     pub(crate) fn run(&self, rt: &RuntimeInner<RT>, id: SignalId<RT>) -> bool {
+        self.run_func(rt, id, !self.listeners.is_empty())
+    }
+
+    /// Computes the value of a lazy signal that is being read
+    pub(crate) fn compute(&self, rt: &RuntimeInner<RT>, id: SignalId<RT>) {
+        self.run_func(rt, id, true);
+    }
+
+    fn run_func(&self, rt: &RuntimeInner<RT>, id: SignalId<RT>, observed: bool) -> bool {
         if let SignalValue::Func(func) = &self.value {
-            let previous = rt.set_running_signal(Some(id));
-            let changed = func.run();
-            // println!("run: {id:?} - {changed}");
+            if !rt.enter_signal(id) {
+                return false;
+            }
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("signal_run", id = ?id).entered();
+            let previous = rt.set_running_signal(None);
+            rt.scope_of(id).run_cleanups(id);
+            self.unsubscribe(rt, id);
+            rt.set_running_signal(Some(id));
+            let changed = func.run(observed);
             rt.set_running_signal(previous);
+            rt.leave_signal();
             changed
         } else {
-            // println!("NOT: {id:?}");
             false
         }
     }
 
+    /// Removes this signal from the listeners of all its sources. The
+    /// sources are re-registered when the signal runs.
+    fn unsubscribe(&self, rt: &RuntimeInner<RT>, id: SignalId<RT>) {
+        for i in 0..self.sources.len() {
+            let source = self.sources.get(i);
+            rt.scope_of(source)
+                .with_signal(source, |sig| sig.listeners.retain(|l| *l != id));
+        }
+        self.sources.clear();
+    }
+
     pub(crate) fn reuse(&mut self) {
         self.listeners.clear();
+        self.sources.clear();
         #[cfg(debug_assertions)]
         {
             self.value = SignalValue::Reuse;
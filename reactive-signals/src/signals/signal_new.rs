@@ -7,44 +7,79 @@ use crate::{
     Signal,
 };
 
-use super::{SignalInner, SignalType, SignalValue};
+use super::{SignalId, SignalIndex, SignalInner, SignalType, SignalValue};
 
 impl<T: 'static + SignalType, RT: Runtime> Signal<T, RT> {
     pub(crate) fn data(sx: Scope<RT>, data: AnyData) -> Signal<T, RT> {
         let id = sx.rt.with_ref(|rt| {
+            rt.check_scope(sx.sx, sx.generation);
+            #[cfg(feature = "snapshots")]
+            rt.value_types.register::<T>();
             let scope = &rt.scope_tree[sx.sx];
             let id = scope.next_signal_id(sx);
             let signal = SignalInner {
                 value: SignalValue::Data(data),
                 listeners: Default::default(),
+                sources: Default::default(),
             };
             scope.insert_signal(signal);
             id
         });
         Signal {
             id,
+            generation: sx.generation,
             ty: PhantomData,
         }
     }
 
     pub(crate) fn func(sx: Scope<RT>, func: impl FnOnce() -> DynFunc) -> Signal<T, RT> {
         let id = sx.rt.with_ref(|rt| {
+            rt.check_scope(sx.sx, sx.generation);
+            #[cfg(feature = "snapshots")]
+            rt.value_types.register::<T>();
             let scope = &rt.scope_tree[sx.sx];
             let id = scope.next_signal_id(sx);
 
-            let previous = rt.set_running_signal(Some(id));
-            let signal = SignalInner {
-                value: SignalValue::Func(func()),
+            // the signal is inserted before the function runs for the first
+            // time so that the signals it reads can register it as a listener.
+            scope.insert_signal(SignalInner {
+                value: SignalValue::Data(AnyData::new(())),
                 listeners: Default::default(),
-            };
+                sources: Default::default(),
+            });
+
+            rt.enter_signal(id);
+            let previous = rt.set_running_signal(Some(id));
+            let value = SignalValue::Func(func());
             rt.set_running_signal(previous);
+            rt.leave_signal();
 
-            scope.insert_signal(signal);
+            rt.scope_of(id).set_signal_value(id, value);
+            rt.check_cycle();
             id
         });
         Signal {
             id,
+            generation: sx.generation,
             ty: PhantomData,
         }
     }
+
+    /// The signal with the index in the scope. It has to have been
+    /// created in the scope with the same signal type.
+    pub(crate) fn from_index(sx: Scope<RT>, index: SignalIndex) -> Signal<T, RT> {
+        Signal {
+            id: SignalId {
+                id: index,
+                sx: sx.sx,
+                rt: sx.rt,
+            },
+            generation: sx.generation,
+            ty: PhantomData,
+        }
+    }
+
+    pub(crate) fn index(&self) -> SignalIndex {
+        self.id.id
+    }
 }
@@ -0,0 +1,37 @@
+use crate::runtimes::Runtime;
+
+use super::{Signal, SignalId, SignalType};
+
+impl<T: SignalType, RT: Runtime> Signal<T, RT> {
+    /// The id of the signal, which can be compared with the ids returned by
+    /// [drain_changed()](crate::runtimes::Runtime::drain_changed()).
+    pub fn id(&self) -> SignalId<RT> {
+        let mut id = self.id;
+        id.set_dirty(false);
+        id
+    }
+
+    /// Registers the signal for polling. Its id is then included in what
+    /// the runtime's [drain_changed()](crate::runtimes::Runtime::drain_changed())
+    /// returns after it changed, once however many times it changed.
+    ///
+    /// This allows grouping the work done on changes, for instance by updating
+    /// the DOM once per frame instead of on every change.
+    ///
+    /// ```rust
+    /// use reactive_signals::{runtimes::{ClientRuntime, Runtime}, signal};
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let double = signal!(sc, move || count.get() * 2);
+    /// double.register_polled();
+    ///
+    /// count.set(2);
+    /// count.set(3);
+    /// assert_eq!(sc.runtime().drain_changed(), vec![double.id()]);
+    /// assert_eq!(sc.runtime().drain_changed(), vec![]);
+    /// ```
+    pub fn register_polled(&self) {
+        self.rt_ref(|rt| rt.register_polled(self.id))
+    }
+}
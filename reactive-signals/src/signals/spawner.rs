@@ -0,0 +1,57 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    primitives::DynFunc,
+    runtimes::{executor, Runtime},
+    scope::RunningSignalGuard,
+    Scope, Signal,
+};
+
+use super::{Func, SignalId};
+
+/// Creates a func signal that spawns the future returned by `spawn` each time
+/// it runs, and cancels the previous one if it hasn't completed.
+pub(crate) fn spawner<S, Fut, RT>(sx: Scope<RT>, spawn: S) -> Signal<Func<()>, RT>
+where
+    S: Fn() -> Fut + 'static,
+    Fut: Future<Output = ()> + 'static,
+    RT: Runtime + Send + Sync + 'static,
+{
+    Signal::func(sx, || {
+        DynFunc::new::<_, (), Func<()>>(move || {
+            let signal = sx.rt.with_ref(|rt| rt.get_running_signal()).unwrap();
+            let task = executor::spawn(
+                sx.rt,
+                Box::pin(Tracked {
+                    signal,
+                    future: Box::pin(spawn()),
+                }),
+            );
+            sx.on_cleanup(move || executor::cancel(sx.rt, task));
+        })
+    })
+}
+
+/// Polls the future as if it was run by the signal that spawned it,
+/// so that the signals it reads re-run the spawner when they change.
+struct Tracked<RT: Runtime> {
+    signal: SignalId<RT>,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+// the future is boxed, so it isn't moved when Tracked is
+impl<RT: Runtime> Unpin for Tracked<RT> {}
+
+impl<RT: Runtime> Future for Tracked<RT> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let _guard = RunningSignalGuard::new(this.signal.rt, Some(this.signal));
+        this.future.as_mut().poll(cx)
+    }
+}
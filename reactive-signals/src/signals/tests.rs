@@ -1,6 +1,11 @@
 use std::{cell::Cell, rc::Rc};
 
-use crate::{runtimes::ServerRuntime, signal, tests::StringStore};
+use crate::{
+    runtimes::{Runtime, ServerRuntime, TestClientRuntime},
+    signal,
+    tests::{Deferred, StringStore},
+    ReactiveError,
+};
 
 #[test]
 fn test_signal_dep() {
@@ -63,7 +68,73 @@ fn test_signal_func_val() {
 
     num_sig.set(4);
 
-    assert_eq!(output.values(), "a5-b5, a4-b5, a4-b4");
+    assert_eq!(output.values(), "a5-b5, a4-b4");
+}
+
+#[test]
+fn test_signal_diamond_runs_once() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    let a_sig = signal!(sc, move || num_sig.get() * 2);
+    let b_sig = signal!(sc, move || num_sig.get() * 3);
+    let c_sig = signal!(sc, move || a_sig.get() + b_sig.get());
+
+    let d_call = Rc::new(Cell::new(0usize));
+    let d_sig = signal!(sc, clone: d_call, move || {
+        d_call.inc();
+        c_sig.get() + num_sig.get()
+    });
+
+    assert_eq!(d_sig.get(), 6);
+    assert_eq!(d_call.get(), 1);
+
+    num_sig.set(2);
+
+    assert_eq!(d_sig.get(), 12);
+    assert_eq!(d_call.get(), 2);
+}
+
+#[test]
+fn test_signal_new_source_during_propagation() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, move || a_sig.get() * 10);
+    let c_sig = signal!(sc, move || if a_sig.get() > 1 { b_sig.get() } else { 0 });
+
+    a_sig.set(2);
+    assert_eq!(b_sig.get(), 20);
+    // b is brought up to date when c starts reading it
+    assert_eq!(c_sig.get(), 20);
+
+    a_sig.set(3);
+    assert_eq!(c_sig.get(), 30);
+}
+
+#[test]
+fn test_signal_new_indirect_source_during_propagation() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, move || a_sig.get() * 10);
+    let d_sig = signal!(sc, move || b_sig.get() + 1);
+
+    let c_call = Rc::new(Cell::new(0usize));
+    let c_sig = signal!(sc, clone: c_call, move || {
+        c_call.inc();
+        if a_sig.get() > 1 {
+            d_sig.get()
+        } else {
+            0
+        }
+    });
+
+    a_sig.set(2);
+    // d is only dirty once b has run, which happens when c reads d
+    assert_eq!(c_sig.get(), 21);
+    assert_eq!(c_call.get(), 2);
 }
 
 #[test]
@@ -109,6 +180,691 @@ fn test_signal_func_skip_equal() {
     assert_eq!(c_call.get(), 1);
 }
 
+#[test]
+fn test_signal_func_eq_stops_propagation() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    // the value only changes when num_sig crosses 10
+    let is_big = signal!(sc, move || num_sig.get() > 10);
+
+    let text_call = Rc::new(Cell::new(0usize));
+    let text_sig = signal!(sc, clone: text_call, move || {
+        text_call.inc();
+        if is_big.get() { "big" } else { "small" }
+    });
+
+    assert_eq!(text_call.get(), 1);
+
+    num_sig.set(2);
+    num_sig.set(3);
+
+    assert_eq!(text_sig.get(), "small");
+    assert_eq!(text_call.get(), 1);
+
+    num_sig.set(11);
+
+    assert_eq!(text_sig.get(), "big");
+    assert_eq!(text_call.get(), 2);
+}
+
+#[test]
+fn test_signal_dynamic_dependencies() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let show = signal!(sc, true);
+    let num_sig = signal!(sc, 1);
+
+    let calls = Rc::new(Cell::new(0usize));
+    let text_sig = signal!(sc, clone: calls, move || {
+        calls.inc();
+        if show.get() {
+            num_sig.get()
+        } else {
+            0
+        }
+    });
+
+    let listeners = |id| {
+        sc.rt
+            .with_ref(|rt| rt.scope_of(id).with_signal(id, |s| s.listeners.len()))
+    };
+    assert_eq!(listeners(num_sig.id), 1);
+
+    show.set(false);
+    assert_eq!(text_sig.get(), 0);
+    assert_eq!(calls.get(), 2);
+    assert_eq!(listeners(num_sig.id), 0);
+
+    // num_sig is not read anymore and doesn't trigger a run
+    num_sig.set(2);
+    assert_eq!(calls.get(), 2);
+
+    show.set(true);
+    assert_eq!(text_sig.get(), 2);
+    assert_eq!(calls.get(), 3);
+    assert_eq!(listeners(num_sig.id), 1);
+    assert_eq!(listeners(show.id), 1);
+}
+
+#[test]
+fn test_signal_untracked() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+    let name_sig = signal!(sc, "a".to_string());
+    let flag_sig = signal!(sc, false);
+
+    let output = Rc::new(StringStore::new());
+    let _text_sig = signal!(sc, clone: output, move || {
+        let name = name_sig.with_untracked(|n| n.clone());
+        let flag = sc.untrack(|| flag_sig.get());
+        output.push(format!("{}{name}{flag}", num_sig.untracked_get() + num_sig.get()))
+    });
+
+    assert_eq!(output.values(), "2afalse");
+
+    name_sig.set("b".to_string());
+    flag_sig.set(true);
+    assert_eq!(output.values(), "2afalse");
+
+    num_sig.set(2);
+    assert_eq!(output.values(), "2afalse, 4btrue");
+}
+
+#[test]
+fn test_signal_untracked_cloned() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+    let name_sig = signal!(sc, "a".to_string());
+    let upper_sig = signal!(sc, move || name_sig.with(|n| n.to_uppercase()));
+
+    let output = Rc::new(StringStore::new());
+    let _text_sig = signal!(sc, clone: output, move || {
+        let name = name_sig.untracked_cloned();
+        let upper = upper_sig.untracked_cloned();
+        output.push(format!("{}{name}{upper}", num_sig.get()))
+    });
+
+    name_sig.set("b".to_string());
+    assert_eq!(output.values(), "1aA");
+
+    num_sig.set(2);
+    assert_eq!(output.values(), "1aA, 2bB");
+}
+
+#[test]
+fn test_signal_untrack_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+    let output = Rc::new(StringStore::new());
+    let _text_sig = signal!(sc, clone: output, move || {
+        let untracked = catch_unwind(AssertUnwindSafe(|| sc.untrack(|| panic!("untracked"))));
+        assert!(untracked.is_err());
+        // the func signal is still subscribed to what it reads after the panic
+        output.push(format!("val: {}", num_sig.get()))
+    });
+
+    num_sig.set(2);
+    assert_eq!(output.values(), "val: 1, val: 2");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "signal cycle detected (scope:signal): 0:0 → 0:1 → 0:0")]
+fn test_signal_write_cycle_panics() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 0);
+    signal!(sc, move || {
+        if num_sig.get() > 10 {
+            num_sig.set(0);
+        }
+    });
+
+    num_sig.set(20);
+}
+
+#[test]
+fn test_signal_write_cycle_error() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 0);
+    let calls = Rc::new(Cell::new(0usize));
+    let child = sc.new_child();
+    signal!(child, clone: calls, move || {
+        calls.inc();
+        let val = num_sig.get();
+        let _ = num_sig.try_set(val + 1);
+    });
+    let Err(ReactiveError::Cycle(err)) = num_sig.try_set(10) else {
+        panic!("expected a cycle error");
+    };
+
+    assert_eq!(err.path(), &[(0, 0), (1, 0), (0, 0)]);
+    assert_eq!(calls.get(), 2);
+    assert_eq!(num_sig.get(), 11);
+
+    // the error is only reported once
+    let err = num_sig.try_update(|v| *v = 20).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "signal cycle detected (scope:signal): 0:0 → 1:0 → 0:0"
+    );
+    assert_eq!(calls.get(), 3);
+    // the update was applied even though the cycle is reported
+    assert_eq!(num_sig.get(), 21);
+}
+
+#[test]
+fn test_signal_read_cycle_error() {
+    use crate::{types::EqFunc, Signal};
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    // a_sig reads b_sig once it is available, and b_sig reads a_sig
+    let b_slot = Rc::new(Cell::new(None::<Signal<EqFunc<i32>, ServerRuntime>>));
+    let a_sig = signal!(sc, clone: b_slot, move || {
+        num_sig.get() + b_slot.get().map(|b| b.get()).unwrap_or(0)
+    });
+    let b_sig = signal!(sc, move || a_sig.get() + 1);
+    b_slot.set(Some(b_sig));
+
+    assert!(num_sig.try_set(2).is_ok());
+
+    let err = num_sig.try_set(3).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "signal cycle detected (scope:signal): 0:1 → 0:2 → 0:1"
+    );
+}
+
+#[test]
+fn test_signal_lazy() {
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let runs = Rc::new(Cell::new(0));
+    let lazy_sig = signal!(sc, lazy, clone: runs, move || {
+        runs.inc();
+        num_sig.get() * 2
+    });
+    // not computed until read
+    assert_eq!(runs.get(), 0);
+
+    assert_eq!(lazy_sig.get(), 2);
+    assert_eq!(lazy_sig.get(), 2);
+    assert_eq!(runs.get(), 1);
+
+    // changes only invalidate the value
+    num_sig.set(2);
+    num_sig.set(3);
+    assert_eq!(runs.get(), 1);
+
+    assert_eq!(lazy_sig.get(), 6);
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn test_signal_lazy_subscribed() {
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let runs = Rc::new(Cell::new(0));
+    let lazy_sig = signal!(sc, lazy, clone: runs, move || {
+        runs.inc();
+        num_sig.get() % 2
+    });
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(format!("{}", lazy_sig.get())));
+    assert_eq!(output.values(), "1");
+    assert_eq!(runs.get(), 1);
+
+    // a lazy signal that is listened to is recomputed when its sources change
+    // and propagation stops when the value is unchanged
+    num_sig.set(3);
+    assert_eq!(runs.get(), 2);
+    assert_eq!(output.values(), "1");
+
+    num_sig.set(4);
+    assert_eq!(runs.get(), 3);
+    assert_eq!(output.values(), "1, 0");
+}
+
+#[test]
+fn test_signal_prev() {
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let sum_sig = signal!(sc, move |prev: Option<&i32>| prev.unwrap_or(&0)
+        + num_sig.get());
+    assert_eq!(sum_sig.get(), 1);
+
+    num_sig.set(2);
+    num_sig.set(3);
+    assert_eq!(sum_sig.get(), 6);
+
+    // keeps the previous value while the source is loading
+    let loaded = signal!(sc, None::<&str>);
+    let shown = signal!(sc, move |prev: Option<&String>| match loaded.get() {
+        Some(v) => v.to_string(),
+        None => prev.cloned().unwrap_or_default(),
+    });
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(shown.cloned()));
+
+    loaded.set(Some("a"));
+    loaded.set(None);
+    loaded.set(Some("b"));
+    assert_eq!(output.values(), ", a, b");
+}
+
+#[test]
+fn test_signal_prev_non_eq() {
+    struct Count(i32);
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let count_sig = signal!(sc, move |prev: Option<&Count>| {
+        num_sig.get();
+        Count(prev.map_or(0, |c| c.0 + 1))
+    });
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(format!("{}", count_sig.with(|c| c.0))));
+
+    num_sig.set(1);
+    num_sig.set(2);
+    assert_eq!(output.values(), "0, 1");
+}
+
+#[test]
+fn test_signal_map() {
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
+    let num_sig = signal!(sc, 1);
+
+    let runs = Rc::new(Cell::new(0));
+    let is_odd = signal!(num_sig.map(|n| n % 2 == 1));
+    let output = Rc::new(StringStore::new());
+    signal!(root, clone: output runs, move || {
+        runs.inc();
+        output.push(format!("{}", is_odd.get()))
+    });
+
+    // propagation stops when the mapped value is unchanged
+    num_sig.set(3);
+    num_sig.set(4);
+    assert_eq!(output.values(), "true, false");
+    assert_eq!(runs.get(), 2);
+
+    // created in the scope of the source signal
+    assert!(is_odd.scope().sx == sc.sx);
+    sc.discard();
+    assert_eq!(is_odd.try_get(), Err(ReactiveError::DiscardedScope));
+}
+
+#[test]
+fn test_signal_combinators_no_eq() {
+    #[derive(Clone)]
+    struct NoEq(u32);
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let wrapped = signal!(num_sig.map(|n| NoEq(n / 2)));
+    let pair_sig = signal!(sc, (NoEq(0), 0));
+    let selected = signal!(pair_sig.select(|p| &p.0));
+    let zipped = signal!(wrapped.zip(selected));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || {
+        let (wrapped, selected) = zipped.cloned();
+        output.push(format!("{} {}", wrapped.0, selected.0))
+    });
+
+    // without PartialEq the subscribers are notified even if the value is the same
+    num_sig.set(0);
+    pair_sig.update(|p| p.1 = 1);
+    pair_sig.update(|p| p.0 = NoEq(2));
+    assert_eq!(output.values(), "0 0, 0 0, 0 0, 0 2");
+}
+
+#[test]
+fn test_signal_zip_select() {
+    let sc = ServerRuntime::new_root_scope();
+    let pair_sig = signal!(sc, (1, "one".to_string()));
+    let flag_sig = signal!(sc, false);
+
+    let name_sig = signal!(pair_sig.select(|p| &p.1));
+    let zipped = signal!(name_sig.zip(flag_sig));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || {
+        let (name, flag) = zipped.cloned();
+        output.push(format!("{name} {flag}"))
+    });
+
+    pair_sig.update(|p| p.0 = 2);
+    assert_eq!(output.values(), "one false");
+
+    pair_sig.update(|p| p.1 = "two".to_string());
+    flag_sig.set(true);
+    assert_eq!(output.values(), "one false, two false, two true");
+}
+
+#[test]
+fn test_signal_async() {
+    let sc = TestClientRuntime::new_root_scope();
+    let id_sig = signal!(sc, 1);
+    let deferred = Rc::new(Deferred::new());
+
+    let name_sig = signal!(sc, clone: deferred, move async || {
+        let id = id_sig.get();
+        format!("{id}: {}", deferred.wait().await)
+    });
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(format!("{:?}", name_sig.cloned())));
+
+    deferred.send("a");
+    assert_eq!(output.values(), "None, Some(\"1: a\")");
+
+    // the running future is cancelled when the closure re-runs
+    id_sig.set(2);
+    id_sig.set(3);
+    deferred.send("b");
+    assert_eq!(output.values(), "None, Some(\"1: a\"), Some(\"3: b\")");
+
+    // an equal value doesn't notify the subscribers
+    id_sig.set(3);
+    deferred.send("b");
+    assert_eq!(output.values(), "None, Some(\"1: a\"), Some(\"3: b\")");
+}
+
+#[test]
+fn test_signal_async_discard() {
+    struct NoEq;
+
+    let root = TestClientRuntime::new_root_scope();
+    let sc = root.new_child();
+    let count_sig = signal!(sc, 0);
+    let deferred = Rc::new(Deferred::<NoEq>::new());
+    let runs = Rc::new(Cell::new(0));
+
+    let no_eq_sig = signal!(sc, clone: deferred, move async || {
+        count_sig.get();
+        deferred.wait().await
+    });
+    signal!(sc, clone: runs, move || no_eq_sig.with(|_| runs.inc()));
+
+    deferred.send(NoEq);
+    count_sig.set(1);
+    deferred.send(NoEq);
+    assert_eq!(runs.get(), 3);
+
+    // the waiting future is dropped with the scope
+    count_sig.set(2);
+    sc.discard();
+    deferred.send(NoEq);
+    assert_eq!(runs.get(), 3);
+}
+
+#[test]
+fn test_signal_async_other_thread() {
+    use std::{cell::RefCell, future::poll_fn, task::Poll};
+
+    let sc = TestClientRuntime::new_root_scope();
+    let value = Rc::new(Cell::new(None));
+    let waker = Rc::new(RefCell::new(None));
+
+    let value_sig = signal!(sc, clone: value waker, move async || {
+        poll_fn(|cx| match value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                *waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await
+    });
+
+    value.set(Some(1));
+    let waker = waker.take().unwrap();
+    std::thread::spawn(move || waker.wake()).join().unwrap();
+    // the future woken on the other thread waits for the runtime's thread
+    assert_eq!(value_sig.get(), None);
+    sc.runtime().run_pending();
+    assert_eq!(value_sig.get(), Some(1));
+}
+
+#[test]
+fn test_signal_async_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let sc = TestClientRuntime::new_root_scope();
+    let panicking = Rc::new(Deferred::<()>::new());
+    let deferred = Rc::new(Deferred::new());
+
+    signal!(sc, clone: panicking, move async || {
+        panicking.wait().await;
+        panic!("polled");
+    });
+    let value_sig = signal!(sc, clone: deferred, move async || deferred.wait().await);
+
+    let sent = catch_unwind(AssertUnwindSafe(|| panicking.send(())));
+    assert!(sent.is_err());
+
+    // the other tasks are still polled
+    deferred.send(1);
+    assert_eq!(value_sig.get(), Some(1));
+}
+
+#[test]
+fn test_signal_async_dropped_with_runtime() {
+    struct OnDrop<F: FnMut()>(F);
+    impl<F: FnMut()> Drop for OnDrop<F> {
+        fn drop(&mut self) {
+            (self.0)()
+        }
+    }
+
+    let sc = TestClientRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let deferred = Rc::new(Deferred::<()>::new());
+    let dropped = Rc::new(Cell::new(None));
+
+    signal!(sc, clone: deferred dropped, move async || {
+        let dropped = dropped.clone();
+        let _on_drop = OnDrop(move || dropped.set(Some(num_sig.try_get())));
+        deferred.wait().await;
+    });
+
+    // the waiting future is dropped once the runtime is no longer borrowed
+    sc.runtime().discard();
+    assert_eq!(dropped.take(), Some(Err(ReactiveError::DiscardedRuntime)));
+}
+
+#[test]
+fn test_signal_timer() {
+    use std::time::Duration;
+
+    let root = TestClientRuntime::new_root_scope();
+    let sc = root.new_child();
+    let rt = sc.runtime();
+    let step_sig = signal!(sc, 100);
+    let output = Rc::new(StringStore::new());
+
+    // the interval doubles after each run, so it runs at 100, 300 and 700 ms
+    let timer_sig = signal!(sc, clone: output, 100 ms, move async |interval| {
+        let step = step_sig.get();
+        output.push(format!("{interval:?}"));
+        *interval = interval.map(|i| i * 2).filter(|i| i.as_millis() <= step * 4);
+    });
+
+    rt.advance(Duration::from_millis(99));
+    assert_eq!(timer_sig.get(), None);
+    rt.advance(Duration::from_millis(601));
+    assert_eq!(timer_sig.get(), Some(()));
+    assert_eq!(output.values(), "Some(100ms), Some(200ms), Some(400ms)");
+
+    // the timer starts over when a signal it reads changes
+    step_sig.set(200);
+    rt.advance(Duration::from_millis(100));
+    assert_eq!(
+        output.values(),
+        "Some(100ms), Some(200ms), Some(400ms), Some(100ms)"
+    );
+
+    // and stops when its scope is discarded
+    sc.discard();
+    rt.advance(Duration::from_secs(1));
+    assert_eq!(
+        output.values(),
+        "Some(100ms), Some(200ms), Some(400ms), Some(100ms)"
+    );
+}
+
+#[test]
+fn test_signal_timer_zero() {
+    use std::time::Duration;
+
+    let sc = TestClientRuntime::new_root_scope();
+    let rt = sc.runtime();
+    let runs = Rc::new(Cell::new(0));
+
+    // a zero interval runs once per millisecond instead of hanging
+    let zero_sig = signal!(sc, clone: runs, 0 ms, move async |_interval| {
+        runs.set(runs.get() + 1);
+    });
+    let reset_sig = signal!(sc, 10 ms, move async |interval| {
+        *interval = Some(Duration::ZERO);
+    });
+
+    rt.advance(Duration::from_millis(12));
+    assert_eq!(runs.get(), 12);
+    assert_eq!(zero_sig.get(), Some(()));
+    assert_eq!(reset_sig.get(), Some(()));
+}
+
+#[test]
+fn test_signal_timer_client() {
+    use crate::runtimes::ClientRuntime;
+    use std::time::Duration;
+
+    let sc = ClientRuntime::new_root_scope();
+    let ms = Duration::from_millis;
+    assert_eq!(ClientRuntime::run_timers(ms(1000)), None);
+
+    // the interval starts at the time of the last run
+    let timer_sig = signal!(sc, 100 ms, move async |interval| *interval = None);
+    assert_eq!(ClientRuntime::run_timers(ms(1050)), Some(ms(50)));
+    assert_eq!(timer_sig.get(), None);
+    // an earlier time is ignored
+    assert_eq!(ClientRuntime::run_timers(ms(0)), Some(ms(50)));
+    assert_eq!(ClientRuntime::run_timers(ms(1100)), None);
+    assert_eq!(timer_sig.get(), Some(()));
+
+    // the timer of a discarded scope is no longer waited for
+    let child = sc.new_child();
+    signal!(child, 100 ms, move async |interval| *interval = None);
+    assert_eq!(ClientRuntime::run_timers(ms(1100)), Some(ms(100)));
+    child.discard();
+    assert_eq!(ClientRuntime::run_timers(ms(1100)), None);
+    sc.discard();
+}
+
+#[test]
+fn test_signal_timer_server() {
+    let sc = ServerRuntime::new_root_scope();
+    let timer_sig = signal!(sc, 0 ms, move async |interval| *interval = None);
+    assert_eq!(timer_sig.get(), None);
+}
+
+#[test]
+fn test_signal_try_accessors() {
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
+    let num_sig = signal!(sc, 1);
+    let str_sig = signal!(sc, "a".to_string());
+
+    assert_eq!(num_sig.try_get(), Ok(1));
+    assert_eq!(num_sig.try_set(2), Ok(()));
+    assert_eq!(num_sig.try_update(|n| *n * 2), Ok(4));
+    assert_eq!(str_sig.try_cloned(), Ok("a".to_string()));
+    assert_eq!(str_sig.try_with(|s| s.len()), Ok(1));
+
+    sc.discard();
+    assert_eq!(num_sig.try_get(), Err(ReactiveError::DiscardedScope));
+    assert_eq!(num_sig.try_set(3), Err(ReactiveError::DiscardedScope));
+    assert_eq!(str_sig.try_cloned(), Err(ReactiveError::DiscardedScope));
+
+    let num_sig = signal!(root, 1);
+    root.discard();
+    assert_eq!(num_sig.try_get(), Err(ReactiveError::DiscardedRuntime));
+}
+
+#[test]
+fn test_signal_try_wrong_runtime() {
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let result = std::thread::spawn(move || num_sig.try_get())
+        .join()
+        .unwrap();
+    assert_eq!(result, Err(ReactiveError::WrongRuntime));
+}
+
+#[cfg(not(feature = "unsafe-cell"))]
+#[test]
+fn test_signal_try_reentrant() {
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+
+    let result = num_sig.with(|_| num_sig.try_set(2));
+    assert_eq!(result, Err(ReactiveError::ReentrantBorrow));
+    assert_eq!(
+        num_sig.update(|_| num_sig.try_get()),
+        Err(ReactiveError::ReentrantBorrow)
+    );
+}
+
+#[test]
+fn test_signal_polled() {
+    let sc = TestClientRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let eq_sig = signal!(sc, move || num_sig.get() > 2);
+    let unpolled_sig = signal!(sc, move || num_sig.get() * 2);
+    num_sig.register_polled();
+    eq_sig.register_polled();
+
+    num_sig.set(2);
+    num_sig.set(3);
+    assert_eq!(unpolled_sig.get(), 6);
+    assert_eq!(sc.rt.drain_changed(), vec![num_sig.id(), eq_sig.id()]);
+    assert_eq!(sc.rt.drain_changed(), vec![]);
+
+    // eq_sig doesn't change
+    num_sig.set(4);
+    assert_eq!(sc.rt.drain_changed(), vec![num_sig.id()]);
+
+    // the signals of a discarded scope are forgotten
+    let child = sc.new_child();
+    let child_sig = signal!(child, move || num_sig.get());
+    child_sig.register_polled();
+    num_sig.set(5);
+    child.discard();
+    assert_eq!(sc.rt.drain_changed(), vec![num_sig.id()]);
+}
+
 trait CellIncr {
     fn inc(&self);
 }
@@ -9,6 +9,7 @@ impl<T> OptReadable for ClientFunc<T> {
 
 impl<T: 'static> SignalType for ClientFunc<T> {
     type Inner = T;
+    const SERVER: bool = false;
 
     fn inner(&self) -> &Self::Inner {
         &self.0
@@ -24,10 +25,14 @@ impl<T: 'static> SignalType for ClientFunc<T> {
 /// A client-side function that produces a value that implements [PartialEq]
 pub struct ClientEqFunc<T>(pub(crate) T);
 
-impl<T> OptReadable for ClientEqFunc<T> {}
+impl<T> OptReadable for ClientEqFunc<T> {
+    const RUN_ON_SERVER: bool = false;
+}
 
 impl<T: 'static + PartialEq> SignalType for ClientEqFunc<T> {
     type Inner = T;
+    const EQ: bool = true;
+    const SERVER: bool = false;
 
     fn is_eq(&self, other: &Self::Inner) -> bool {
         self.0 == *other
@@ -33,6 +33,7 @@ impl<T> Readable for EqData<T> {}
 
 impl<T: 'static + PartialEq> SignalType for EqData<T> {
     type Inner = T;
+    const EQ: bool = true;
     fn is_eq(&self, other: &Self::Inner) -> bool {
         self.0 == *other
     }
@@ -58,6 +59,7 @@ impl<T> Readable for HashEqData<T> {}
 
 impl<T: 'static + PartialEq + Hash> SignalType for HashEqData<T> {
     type Inner = T;
+    const EQ: bool = true;
     fn is_eq(&self, other: &Self::Inner) -> bool {
         self.0 == *other
     }
@@ -26,6 +26,7 @@ impl<T> Readable for EqFunc<T> {}
 
 impl<T: 'static + PartialEq> SignalType for EqFunc<T> {
     type Inner = T;
+    const EQ: bool = true;
 
     fn is_eq(&self, other: &Self::Inner) -> bool {
         self.0 == *other
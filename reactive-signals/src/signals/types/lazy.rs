@@ -0,0 +1,78 @@
+use super::{Readable, SignalType};
+
+/// A lazy function that produces a value that doesn't implement [PartialEq].
+///
+/// The value is only computed when read and is discarded when any of the
+/// signals it depends on change.
+pub struct LazyFunc<T>(pub(crate) Option<T>);
+
+impl<T> Readable for LazyFunc<T> {}
+
+impl<T: 'static> SignalType for LazyFunc<T> {
+    type Inner = T;
+    const LAZY: bool = true;
+
+    fn is_stale(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        self.0.as_ref().expect("BUG: reading a stale lazy signal")
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self.0.as_mut().expect("BUG: reading a stale lazy signal")
+    }
+    fn new(value: Self::Inner) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<T: 'static> LazySignalType for LazyFunc<T> {
+    fn stale() -> Self {
+        Self(None)
+    }
+}
+
+/// A lazy function that produces a value that implements [PartialEq].
+///
+/// The value is only computed when read and is discarded when any of the
+/// signals it depends on change.
+pub struct LazyEqFunc<T>(pub(crate) Option<T>);
+
+impl<T> Readable for LazyEqFunc<T> {}
+
+impl<T: 'static + PartialEq> SignalType for LazyEqFunc<T> {
+    type Inner = T;
+    const LAZY: bool = true;
+    const EQ: bool = true;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        self.0.as_ref() == Some(other)
+    }
+
+    fn is_stale(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        self.0.as_ref().expect("BUG: reading a stale lazy signal")
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self.0.as_mut().expect("BUG: reading a stale lazy signal")
+    }
+    fn new(value: Self::Inner) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<T: 'static + PartialEq> LazySignalType for LazyEqFunc<T> {
+    fn stale() -> Self {
+        Self(None)
+    }
+}
+
+#[doc(hidden)]
+pub trait LazySignalType: SignalType {
+    /// A value that has not been computed yet
+    fn stale() -> Self;
+}
@@ -4,20 +4,33 @@
 mod client;
 mod data;
 mod func;
+mod lazy;
 mod server;
 
 pub use client::*;
 pub use data::*;
 pub use func::*;
+pub use lazy::*;
 pub use server::*;
 
 #[doc(hidden)]
 pub trait SignalType: 'static {
     type Inner;
+    /// Lazy signals are only computed when read
+    const LAZY: bool = false;
+    /// Whether the subscribers are only notified when the value changed
+    const EQ: bool = false;
+    /// Whether the signal runs on the server
+    const SERVER: bool = true;
+    /// Whether the signal runs on the client
+    const CLIENT: bool = true;
 
     fn is_eq(&self, _other: &Self::Inner) -> bool {
         false
     }
+    fn is_stale(&self) -> bool {
+        false
+    }
     fn opt_hash(&self) -> Option<u64> {
         None
     }
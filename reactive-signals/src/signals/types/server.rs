@@ -9,6 +9,7 @@ impl<T> OptReadable for ServerFunc<T> {
 
 impl<T: 'static> SignalType for ServerFunc<T> {
     type Inner = T;
+    const CLIENT: bool = false;
 
     fn inner(&self) -> &Self::Inner {
         &self.0
@@ -24,10 +25,14 @@ impl<T: 'static> SignalType for ServerFunc<T> {
 /// A server-side function that produces a value that implements [PartialEq]
 pub struct ServerEqFunc<T>(pub(crate) T);
 
-impl<T> OptReadable for ServerEqFunc<T> {}
+impl<T> OptReadable for ServerEqFunc<T> {
+    const RUN_ON_CLIENT: bool = false;
+}
 
 impl<T: 'static + PartialEq> SignalType for ServerEqFunc<T> {
     type Inner = T;
+    const EQ: bool = true;
+    const CLIENT: bool = false;
 
     fn is_eq(&self, other: &Self::Inner) -> bool {
         self.0 == *other
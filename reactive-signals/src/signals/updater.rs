@@ -1,17 +1,131 @@
 use crate::{
-    iter::{NodeResolver, VecTreeIter},
+    arena_tree::Tree,
+    error::CycleError,
+    iter::{IdVecIter, NodeResolver},
     runtimes::{Runtime, RuntimeInner},
     signals::SignalId,
+    ScopeInner,
 };
 
+/// Propagates a change of `sig` to all the signals that depend on it, directly
+/// or indirectly, using a push-pull algorithm:
+///
+/// - push: all the dependent signals are collected in topological order, i.e. a signal
+///   always comes after all the signals it depends on.
+/// - pull: the signals are visited in that order and a signal only runs if at least one of
+///   the signals it depends on changed. A signal that is read before it is visited, e.g.
+///   by a func signal that starts depending on it, is brought up to date when it is read.
+///
+/// This guarantees that a signal runs at most once per change and never sees a partially
+/// updated graph.
 pub(crate) fn propagate_change<RT: Runtime>(rt: &RuntimeInner<RT>, sig: SignalId<RT>) {
-    let tree = &rt.scope_tree;
-    let mut iter = VecTreeIter::new(tree, sig);
+    propagate_changes(rt, &[sig]);
+}
+
+/// Same as [propagate_change] but for several changed signals at once. A signal that
+/// depends on more than one of them still runs at most once.
+pub(crate) fn propagate_changes<RT: Runtime>(rt: &RuntimeInner<RT>, sigs: &[SignalId<RT>]) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("propagate_change", changed = ?sigs).entered();
+    let (order, pending, cycle) = dependents_in_order(&rt.scope_tree, sigs);
+    if let Some(cycle) = cycle {
+        rt.set_cycle_error(cycle);
+    }
+
+    let outer = rt.replace_pending(pending);
+    sigs.iter().for_each(|sig| rt.mark_listeners_dirty(*sig));
+
+    for id in order {
+        update_pending(rt, id, &mut Vec::new());
+    }
+    rt.replace_pending(outer);
+}
+
+/// Brings a signal up to date if the current propagation hasn't done so yet. Its
+/// sources are brought up to date first, after which it runs if one of them changed.
+#[inline]
+pub(crate) fn update_if_pending<RT: Runtime>(rt: &RuntimeInner<RT>, id: SignalId<RT>) {
+    if rt.is_pending(id) {
+        update_pending(rt, id, &mut Vec::new());
+    }
+}
+
+/// The path holds the signals whose sources are being brought up to date, which
+/// stops the recursion on a cycle. The cycle has been reported when collecting
+/// the dependents.
+fn update_pending<RT: Runtime>(
+    rt: &RuntimeInner<RT>,
+    id: SignalId<RT>,
+    path: &mut Vec<SignalId<RT>>,
+) {
+    if !rt.is_pending(id) || path.contains(&id) {
+        return;
+    }
+    let sources = rt.scope_of(id).with_signal(id, |sig| {
+        (0..sig.sources.len())
+            .map(|i| sig.sources.get(i))
+            .collect::<Vec<_>>()
+    });
+    path.push(id);
+    sources
+        .into_iter()
+        .for_each(|source| update_pending(rt, source, path));
+    path.pop();
+
+    if rt.take_pending(id) && rt.scope_tree.node(id).run(rt, id) {
+        rt.record_change(id);
+        rt.mark_listeners_dirty(id);
+    }
+}
+
+/// Returns all the signals depending on `sigs` in topological order together with the
+/// same signals, and `sigs`, sorted by id (which allows a fast lookup of the dirty flag).
+///
+/// The topological order is the reversed post-order of a depth-first search. While searching,
+/// the flag of the visited signals marks the ones on the stack, which is used for detecting
+/// cycles. A cycle is reported and the edge closing it is ignored.
+fn dependents_in_order<RT: Runtime>(
+    tree: &Tree<ScopeInner<RT>>,
+    sigs: &[SignalId<RT>],
+) -> (Vec<SignalId<RT>>, Vec<SignalId<RT>>, Option<CycleError>) {
+    let mut visited: Vec<SignalId<RT>> = Vec::new();
+    let mut post_order = Vec::new();
+    let mut cycle = None;
+
+    for &sig in sigs {
+        match visited.binary_search(&sig) {
+            Ok(_) => continue,
+            Err(pos) => visited.insert(pos, sig),
+        }
+        let mut stack = vec![(sig, IdVecIter::new(tree.node(sig)))];
 
-    while let Some(next) = iter.next() {
-        // println!("upd: {next:?}");
-        if !tree.node(next).run(rt, next) {
-            iter.skip_children();
+        while let Some((_, listeners)) = stack.last_mut() {
+            if let Some(mut next) = listeners.next() {
+                match visited.binary_search(&next) {
+                    Err(pos) => {
+                        next.set_dirty(true);
+                        visited.insert(pos, next);
+                        stack.push((next, IdVecIter::new(tree.node(next))));
+                    }
+                    Ok(pos) if visited[pos].is_dirty() && cycle.is_none() => {
+                        let start = stack.iter().position(|(id, _)| *id == next).unwrap();
+                        let mut path: Vec<_> = stack[start..].iter().map(|(id, _)| *id).collect();
+                        path.push(next);
+                        cycle = Some(CycleError::new(&path));
+                    }
+                    Ok(_) => {}
+                }
+            } else if let Some((id, _)) = stack.pop() {
+                // the changed signal itself is not a dependent
+                if !stack.is_empty() {
+                    if let Ok(pos) = visited.binary_search(&id) {
+                        visited[pos].set_dirty(false);
+                    }
+                    post_order.push(id);
+                }
+            }
         }
     }
+    post_order.reverse();
+    (post_order, visited, cycle)
 }
@@ -0,0 +1,132 @@
+use crate::{
+    primitives::AnyData,
+    runtimes::Runtime,
+    signals::{Data, EqData},
+    Scope, Signal,
+};
+
+/// A struct that can be turned into a reactive store with a signal for each field,
+/// so that reading a field only subscribes to that field.
+///
+/// It is implemented with `#[derive(Store)]`, which generates a `{Name}Store` type with:
+/// - `new(scope, value)`: creates the store, which can also be done with
+///   [into_store()](Self::into_store()).
+/// - a function for each field, named as the field, returning its signal.
+/// - `set(value)`: sets all the fields. Only the subscribers of the changed fields are notified.
+/// - `cloned()`: a clone of the whole value, subscribing to all the fields.
+/// - `update(|value| ..)`: applies the function to a clone of the whole value and sets it.
+///
+/// The fields have to implement [Clone] and they are stored in
+/// [EqData](crate::types::EqData) signals, which requires them to implement [PartialEq]. Use
+/// `#[store(no_eq)]` for a field that doesn't, and `#[store(nested)]` for a field whose
+/// type derives Store in order to get its store instead of a signal.
+///
+/// ```rust
+/// use reactive_signals::{runtimes::ClientRuntime, signal, Store};
+///
+/// #[derive(Store, Clone)]
+/// struct Window {
+///     width: u32,
+///     height: u32,
+/// }
+///
+/// #[derive(Store, Clone)]
+/// struct Settings {
+///     theme: String,
+///     #[store(nested)]
+///     window: Window,
+/// }
+///
+/// let sc = ClientRuntime::new_root_scope();
+/// let settings = Settings {
+///     theme: "dark".to_string(),
+///     window: Window { width: 800, height: 600 },
+/// }
+/// .into_store(sc);
+///
+/// let themes = signal!(sc, Vec::<String>::new());
+/// signal!(sc, move || {
+///     let theme = settings.theme().cloned();
+///     themes.update(|t| t.push(theme.clone()));
+/// });
+///
+/// // the func signal only reads the theme
+/// settings.window().width().set(1024);
+/// settings.update(|s| s.theme = "light".to_string());
+///
+/// assert_eq!(themes.cloned(), vec!["dark", "light"]);
+/// assert_eq!(settings.cloned().window.width, 1024);
+/// ```
+pub trait Store: Sized + 'static {
+    type Store<RT: Runtime>: Copy;
+
+    fn into_store<RT: Runtime>(self, sx: Scope<RT>) -> Self::Store<RT>;
+
+    #[doc(hidden)]
+    fn set_store<RT: Runtime>(store: &Self::Store<RT>, value: Self);
+
+    #[doc(hidden)]
+    fn from_store<RT: Runtime>(store: &Self::Store<RT>) -> Self;
+}
+
+#[doc(hidden)]
+pub fn eq_data<T: PartialEq + 'static, RT: Runtime>(
+    sx: Scope<RT>,
+    value: T,
+) -> Signal<EqData<T>, RT> {
+    Signal::data(sx, AnyData::new(EqData(value)))
+}
+
+#[doc(hidden)]
+pub fn data<T: 'static, RT: Runtime>(sx: Scope<RT>, value: T) -> Signal<Data<T>, RT> {
+    Signal::data(sx, AnyData::new(Data(value)))
+}
+
+#[test]
+fn test_store() {
+    use crate::{runtimes::ServerRuntime, signal, tests::StringStore, Store};
+    use std::rc::Rc;
+
+    struct NoEq(u8);
+
+    #[derive(Store, Clone, PartialEq, Debug)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Store)]
+    struct Shape {
+        name: &'static str,
+        #[store(no_eq)]
+        tag: Rc<NoEq>,
+        #[store(nested)]
+        origin: Point,
+    }
+
+    let sc = ServerRuntime::new_root_scope();
+    let shape = ShapeStore::new(
+        sc,
+        Shape {
+            name: "square",
+            tag: Rc::new(NoEq(1)),
+            origin: Point { x: 0, y: 0 },
+        },
+    );
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output.push(format!("name: {}", shape.name().get())));
+    signal!(sc, clone: output, move || output.push(format!("x: {}", shape.origin().x().get())));
+    signal!(sc, clone: output, move || output.push(format!("tag: {}", shape.tag().with(|t| t.0))));
+
+    // setting the whole value only notifies the changed fields
+    // and the fields without equality
+    shape.set(Shape {
+        name: "square",
+        tag: Rc::new(NoEq(2)),
+        origin: Point { x: 1, y: 0 },
+    });
+    shape.origin().set(Point { x: 1, y: 2 });
+    assert_eq!(output.values(), "name: square, x: 0, tag: 1, x: 1, tag: 2");
+    assert_eq!(shape.origin().cloned(), Point { x: 1, y: 2 });
+}
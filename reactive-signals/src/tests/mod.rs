@@ -1,6 +1,8 @@
 pub mod profile;
 #[cfg(test)]
 mod size_test;
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_test;
 
 use std::cell::RefCell;
 
@@ -24,3 +26,37 @@ impl StringStore {
             .join(", ")
     }
 }
+
+/// A value that is sent from a test to the futures waiting for it
+#[derive(Default)]
+pub struct Deferred<T> {
+    value: RefCell<Option<T>>,
+    waker: RefCell<Option<std::task::Waker>>,
+}
+
+impl<T> Deferred<T> {
+    pub fn new() -> Self {
+        Self {
+            value: RefCell::new(None),
+            waker: RefCell::new(None),
+        }
+    }
+
+    /// Wakes the future waiting for the value, if any
+    pub fn send(&self, value: T) {
+        *self.value.borrow_mut() = Some(value);
+        let waker = self.waker.borrow_mut().take();
+        waker.into_iter().for_each(|w| w.wake());
+    }
+
+    pub async fn wait(&self) -> T {
+        std::future::poll_fn(|cx| match self.value.borrow_mut().take() {
+            Some(value) => std::task::Poll::Ready(value),
+            None => {
+                *self.waker.borrow_mut() = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        })
+        .await
+    }
+}
@@ -29,10 +29,17 @@ fn wasm_sizes() {
     assert_eq!(mem::size_of::<SignalValue>(), size);
 
     // SignalSet: RefCell & Vec
+    #[cfg(not(feature = "large-ids"))]
     assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 20);
+    // the array of 8 byte ids is larger than the Vec and needs a discriminant
+    #[cfg(feature = "large-ids")]
+    assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 32);
 
-    // SignalInner: SignalValue + SignalSet
-    let size = if cfg!(debug_assertions) { 40 } else { 36 };
+    // SignalInner: SignalValue + 2 * SignalSet
+    #[cfg(not(feature = "large-ids"))]
+    let size = if cfg!(debug_assertions) { 60 } else { 56 };
+    #[cfg(feature = "large-ids")]
+    let size = if cfg!(debug_assertions) { 84 } else { 80 };
     assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), size);
 }
 
@@ -53,13 +60,34 @@ fn wasm_sizes() {
     assert_eq!(mem::size_of::<SignalValue>(), size);
 
     // SignalSet: UnsafeCell & Vec
+    #[cfg(not(feature = "large-ids"))]
     assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 16);
+    #[cfg(feature = "large-ids")]
+    assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 28);
 
-    // SignalInner: SignalValue + SignalSet
-    let size = if cfg!(debug_assertions) { 36 } else { 32 };
+    // SignalInner: SignalValue + 2 * SignalSet
+    #[cfg(not(feature = "large-ids"))]
+    let size = if cfg!(debug_assertions) { 52 } else { 48 };
+    #[cfg(feature = "large-ids")]
+    let size = if cfg!(debug_assertions) { 76 } else { 72 };
     assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), size);
 }
 
+/// Run with both:
+///  - cargo test --target wasm32-unknown-unknown [--features=large-ids]
+///  - cargo test --target wasm32-unknown-unknown [--features=large-ids] --profile=release
+#[wasm_bindgen_test]
+fn wasm_id_sizes() {
+    #[cfg(not(feature = "large-ids"))]
+    assert_eq!(mem::size_of::<SignalId<ClientRuntime>>(), 4);
+    #[cfg(feature = "large-ids")]
+    assert_eq!(mem::size_of::<SignalId<ClientRuntime>>(), 8);
+    assert_eq!(
+        mem::size_of::<SignalId<ClientRuntime>>(),
+        mem::size_of::<Option<SignalId<ClientRuntime>>>()
+    );
+}
+
 /// Test sizes of ArrVec enum
 #[allow(dead_code, non_camel_case_types)]
 #[wasm_bindgen_test]
@@ -97,3 +125,25 @@ fn test_subscriber_size() {
     }
     assert_eq!(mem::size_of::<Store_u15_RT_small>(), 16);
 }
+
+/// The sizes with the `large-ids` feature on a 64 bit host, as the
+/// ones above are only checked when running on wasm.
+#[cfg(all(feature = "large-ids", target_pointer_width = "64"))]
+#[test]
+fn host_large_id_sizes() {
+    assert_eq!(mem::size_of::<crate::arena_tree::NodeId>(), 4);
+    assert_eq!(mem::size_of::<SignalId<ClientRuntime>>(), 8);
+    assert_eq!(mem::size_of::<Option<SignalId<ClientRuntime>>>(), 8);
+
+    // SignalSet: the array of 3 ids, and its discriminant, is larger than the Vec
+    #[cfg(not(feature = "unsafe-cell"))]
+    assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 40);
+    #[cfg(feature = "unsafe-cell")]
+    assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 32);
+
+    // SignalInner: SignalValue + 2 * SignalSet
+    #[cfg(not(feature = "unsafe-cell"))]
+    assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), 120);
+    #[cfg(feature = "unsafe-cell")]
+    assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), 104);
+}
@@ -0,0 +1,111 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+use crate::{
+    runtimes::{ClientRuntime, ServerRuntime, TestClientRuntime},
+    signal,
+};
+
+/// Records the names of the spans entered and the messages of the events
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<(u64, String)>>>,
+    log: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let name = span.metadata().name().to_string();
+        self.spans.lock().unwrap().push((id, name));
+        span::Id::from_u64(id)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        struct Message(String);
+        impl Visit for Message {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{value:?}");
+                }
+            }
+        }
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        self.log.lock().unwrap().push(message.0);
+    }
+
+    fn enter(&self, span: &span::Id) {
+        let spans = self.spans.lock().unwrap();
+        let (_, name) = spans.iter().find(|(id, _)| *id == span.into_u64()).unwrap();
+        self.log.lock().unwrap().push(name.clone());
+    }
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn test_tracing() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let sc = ServerRuntime::new_root_scope();
+        let child = sc.new_child();
+        let num_sig = signal!(sc, 1);
+        let _double = signal!(child, move || num_sig.get() * 2);
+        assert_eq!(
+            recorder.log.lock().unwrap().drain(..).collect::<Vec<_>>(),
+            vec!["root scope created", "scope created"]
+        );
+
+        num_sig.set(2);
+        child.discard();
+        sc.discard();
+    });
+    assert_eq!(
+        *recorder.log.lock().unwrap(),
+        vec![
+            "propagate_change",
+            "signal_run",
+            "scope discarded",
+            "scope discarded"
+        ]
+    );
+}
+
+#[test]
+fn test_tracing_root_scopes() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        // the first root scopes create their runtime, the later ones reuse it
+        ServerRuntime::new_root_scope().discard();
+        ServerRuntime::new_root_scope().discard();
+        TestClientRuntime::new_root_scope().discard();
+        TestClientRuntime::new_root_scope().discard();
+        ClientRuntime::new_root_scope().discard();
+        ClientRuntime::new_root_scope().discard();
+    });
+    let created = recorder
+        .log
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|m| *m == "root scope created")
+        .count();
+    assert_eq!(created, 6);
+}